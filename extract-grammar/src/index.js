@@ -375,6 +375,10 @@ const buildGrammar = parsedGrammar => {
         name: 'value',
         kind: 'IRI',
         required: true,
+      },
+      {
+        name: 'language',
+        kind: 'LanguageTag',
       }
     ]
   });
@@ -397,6 +401,7 @@ const buildGrammar = parsedGrammar => {
       {
         name: 'datatypes',
         kind: 'DatatypeExpression[]',
+        minCardinality: 2,
       }
     ]
   });
@@ -410,6 +415,7 @@ const buildGrammar = parsedGrammar => {
       {
         name: 'datatypes',
         kind: 'DatatypeExpression[]',
+        minCardinality: 2,
       }
     ]
   });
@@ -436,6 +442,7 @@ const buildGrammar = parsedGrammar => {
       {
         name: 'values',
         kind: 'Literal[]',
+        minCardinality: 1,
       }
     ]
   });
@@ -1,4 +1,3 @@
-extern crate cid;
 extern crate integer_encoding;
 extern crate itertools;
 extern crate multibase;
@@ -13,10 +12,9 @@ use cid_fork_rlay::ToCid;
 use integer_encoding::VarInt;
 use itertools::Itertools;
 use multibase::{encode as base_encode, Base};
-use prost::Message;
 use rlay_ontology::prelude::*;
+use rlay_ontology::vocab;
 use rustc_hex::FromHex;
-use rustc_hex::ToHex;
 use std::collections::BTreeMap;
 
 pub struct AnnotationMap(BTreeMap<Vec<u8>, Annotation>);
@@ -74,7 +72,7 @@ pub fn main() {
 
     let raw_value = vec![0xa2, 0x01, 0x41, 0xab, 0x02, 0x41, 0x45];
     let value: AnnotationFormatCompact = serde_cbor::from_slice(&raw_value).unwrap();
-    let value = Annotation::from_compact_format(value).to_web3_format();
+    let value = FormatWeb3(Annotation::from_compact_format(value));
     println!("{}", serde_json::to_string_pretty(&value).unwrap());
 
     let value: AnnotationFormatCompact = serde_cbor::from_slice(&raw_value).unwrap();
@@ -87,46 +85,31 @@ pub fn main() {
     let entity_v0 = EntityV0::deserialize(&mut std::io::Cursor::new(entity_serialized)).unwrap();
     println!("{:?}", &entity_v0);
 
-    // println!(
-    // "{}",
-    // SolidityBytesChunked(
-    // &rlay_ontology::create_label_annotation(String::new())
-    // .unwrap()
-    // .property
-    // )
-    // );
-    // println!(
-    // "\"label\" annotation property: {}",
-    // SolidityBytes(
-    // &rlay_ontology::create_label_annotation(String::new())
-    // .unwrap()
-    // .property
-    // ),
-    // );
-    // let label_annotation = rlay_ontology::create_label_annotation("Organization".to_owned())
-    // .unwrap()
-    // .to_cid()
-    // .unwrap();
-    // let label_hash = label_annotation.to_bytes();
-    // println!("Byte part cid: {}", SolidityBytes(&label_annotation.hash));
-    // println!("Full cid: {}", SolidityBytes(&label_hash));
-    // println!("Full cid: {}", SolidityBytesChunked(&label_hash));
-    // let base58_label_hash = base_encode(Base::Base58btc, &label_hash);
-    // println!("Full cid (base58btc): {}", base58_label_hash);
-
-    // let mut organization = Class::default();
-    // organization.annotations.push(label_hash);
-    // let organization_cid = organization.to_cid().unwrap();
-    // let organization_hash = organization_cid.to_bytes();
-    // println!("======= Class Organization ======");
-    // println!("Byte part cid: {}", SolidityBytes(&organization_cid.hash));
-    // println!("Full cid: {}", SolidityBytes(&organization_hash));
-
-    // let mut company = Class::default();
-    // company.sub_class_of_class.push(organization_hash);
-    // let company_cid = company.to_cid().unwrap();
-    // let company_hash = company_cid.to_bytes();
-    // println!("======= Class Company ======");
-    // println!("Byte part cid: {}", SolidityBytes(&company_cid.hash));
-    // println!("Full cid: {}", SolidityBytes(&company_hash));
+    println!(
+        "\"label\" annotation property: {}",
+        SolidityBytes(vocab::RDFS_LABEL)
+    );
+    let label_annotation = vocab::label("Organization").to_cid().unwrap();
+    let label_hash = label_annotation.to_bytes();
+    println!("Byte part cid: {}", SolidityBytes(&label_annotation.hash));
+    println!("Full cid: {}", SolidityBytes(&label_hash));
+    println!("Full cid: {}", SolidityBytesChunked(&label_hash));
+    let base58_label_hash = base_encode(Base::Base58btc, &label_hash);
+    println!("Full cid (base58btc): {}", base58_label_hash);
+
+    let mut organization = Class::default();
+    organization.annotations.push(label_hash);
+    let organization_cid = organization.to_cid().unwrap();
+    let organization_hash = organization_cid.to_bytes();
+    println!("======= Class Organization ======");
+    println!("Byte part cid: {}", SolidityBytes(&organization_cid.hash));
+    println!("Full cid: {}", SolidityBytes(&organization_hash));
+
+    let mut company = Class::default();
+    company.super_class_expression.push(organization_hash);
+    let company_cid = company.to_cid().unwrap();
+    let company_hash = company_cid.to_bytes();
+    println!("======= Class Company ======");
+    println!("Byte part cid: {}", SolidityBytes(&company_cid.hash));
+    println!("Full cid: {}", SolidityBytes(&company_hash));
 }
@@ -0,0 +1,131 @@
+//! Detection of contradictions between positive and negative assertions.
+//!
+//! An assertion contradicts its complementary negative assertion (see
+//! `Entity::assertion_complement`) if both state the same subject, predicate and object.
+//! Annotations on the assertions are not taken into account.
+use crate::ontology::{Entity, EntityKind, Polarity};
+#[cfg(feature = "reasoner")]
+use crate::reasoner::Realization;
+use std::collections::{BTreeMap, BTreeSet};
+
+type CidBytes = Vec<u8>;
+
+/// A pair of contradicting assertions.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Conflict {
+    /// Kind of the positive assertion.
+    pub family: EntityKind,
+    /// CID of the positive assertion.
+    pub positive: CidBytes,
+    /// CID of the negative assertion.
+    pub negative: CidBytes,
+    /// Whether the positive assertion was inferred instead of asserted.
+    pub inferred: bool,
+}
+
+/// Kind of the positive assertion, subject, predicate (class or property) and object of an
+/// assertion.
+type AssertionKey = (
+    EntityKind,
+    Option<CidBytes>,
+    Option<CidBytes>,
+    Option<CidBytes>,
+);
+
+/// The key shared by the entity and its complement, and the polarity of the entity, if it is an
+/// assertion that has a complement.
+fn assertion_key(entity: &Entity) -> Option<(AssertionKey, bool)> {
+    let assertion = entity.as_assertion()?;
+    let (positive_kind, is_positive) = match entity.polarity() {
+        Polarity::Positive => (entity.kind(), true),
+        Polarity::Negative => (entity.assertion_complement()?.kind(), false),
+        Polarity::NotAnAssertion => return None,
+    };
+    let key = (
+        positive_kind,
+        assertion.subject().cloned(),
+        assertion.predicate().cloned(),
+        assertion.object().cloned(),
+    );
+    Some((key, is_positive))
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConsistencyChecker {
+    positive: BTreeMap<AssertionKey, BTreeSet<CidBytes>>,
+    negative: BTreeMap<AssertionKey, BTreeSet<CidBytes>>,
+}
+
+impl ConsistencyChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects all assertions in `entities`, ignoring all other kinds.
+    pub fn from_entities<'a, I: IntoIterator<Item = &'a Entity>>(entities: I) -> Self {
+        let mut checker = Self::new();
+        for entity in entities {
+            checker.insert_entity(entity);
+        }
+        checker
+    }
+
+    /// Collects the entity if it is an assertion that has a complement. Returns whether it was.
+    pub fn insert_entity(&mut self, entity: &Entity) -> bool {
+        let (key, is_positive) = match assertion_key(entity) {
+            Some(key) => key,
+            None => return false,
+        };
+        let index = match is_positive {
+            true => &mut self.positive,
+            false => &mut self.negative,
+        };
+        index.entry(key).or_default().insert(entity.to_bytes());
+        true
+    }
+
+    /// Returns every pair of an assertion and a negative assertion that contradict each other,
+    /// in a deterministic order.
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        for (key, positives) in self.positive.iter() {
+            let negatives = match self.negative.get(key) {
+                Some(negatives) => negatives,
+                None => continue,
+            };
+            for positive in positives.iter() {
+                for negative in negatives.iter() {
+                    conflicts.push(Conflict {
+                        family: key.0.clone(),
+                        positive: positive.clone(),
+                        negative: negative.clone(),
+                        inferred: false,
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Returns every pair of an inferred class assertion and a negative class assertion that
+    /// contradict each other. The positive CID is the one of the inferred `ClassAssertion`.
+    #[cfg(feature = "reasoner")]
+    pub fn conflicts_with_realization(&self, realization: &Realization) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        for inferred in realization.inferred_class_assertions() {
+            let entity = Entity::ClassAssertion(inferred.assertion.clone());
+            let (key, _) = assertion_key(&entity).unwrap();
+            if let Some(negatives) = self.negative.get(&key) {
+                for negative in negatives.iter() {
+                    conflicts.push(Conflict {
+                        family: key.0.clone(),
+                        positive: inferred.cid.clone(),
+                        negative: negative.clone(),
+                        inferred: true,
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+}
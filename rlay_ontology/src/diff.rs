@@ -0,0 +1,234 @@
+//! Differences between two releases of an ontology.
+//!
+//! As entities are content-addressed, editing an entity removes the old CID and adds a new one.
+//! [`diff`] pairs such removals and additions up as modifications if the two entities share an
+//! identity key and the pairing is unambiguous:
+//!
+//! - assertions are identified by their subject and predicate (class or property),
+//! - other entities are identified by the values of their `rdfs:label` annotations.
+//!
+//! The diff serializes to JSON with all CIDs as base58btc-encoded multibase strings.
+use crate::ontology::{Entity, FieldValue};
+use crate::vocab;
+use multibase::Base;
+use serde::Serializer;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+type CidBytes = Vec<u8>;
+
+fn encode_cid(cid: &[u8]) -> String {
+    multibase::encode(Base::Base58btc, cid)
+}
+
+fn serialize_cid<S: Serializer>(cid: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&encode_cid(cid))
+}
+
+fn serialize_cids<S: Serializer>(cids: &[CidBytes], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(cids.iter().map(|cid| encode_cid(cid)))
+}
+
+/// An entity that was replaced by an entity with the same identity.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Modification {
+    #[serde(serialize_with = "serialize_cid")]
+    pub old: CidBytes,
+    #[serde(serialize_with = "serialize_cid")]
+    pub new: CidBytes,
+    /// Names of the fields whose values differ, in declaration order.
+    pub changed_fields: Vec<&'static str>,
+}
+
+/// Changes to the entities of a single kind, each sorted by CID.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct KindDiff {
+    #[serde(serialize_with = "serialize_cids")]
+    pub added: Vec<CidBytes>,
+    #[serde(serialize_with = "serialize_cids")]
+    pub removed: Vec<CidBytes>,
+    pub modified: Vec<Modification>,
+}
+
+impl KindDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Changes between two entity collections, by kind name. Kinds without changes are left out.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct OntologyDiff {
+    pub kinds: BTreeMap<&'static str, KindDiff>,
+}
+
+impl OntologyDiff {
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty()
+    }
+
+    /// Changes to the entities of the kind, if there are any.
+    pub fn kind(&self, name: &str) -> Option<&KindDiff> {
+        self.kinds.get(name)
+    }
+}
+
+impl fmt::Display for OntologyDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (kind, changes) in self.kinds.iter() {
+            writeln!(f, "{}:", kind)?;
+            for cid in changes.added.iter() {
+                writeln!(f, "  + {}", encode_cid(cid))?;
+            }
+            for cid in changes.removed.iter() {
+                writeln!(f, "  - {}", encode_cid(cid))?;
+            }
+            for modification in changes.modified.iter() {
+                writeln!(
+                    f,
+                    "  ~ {} -> {} ({})",
+                    encode_cid(&modification.old),
+                    encode_cid(&modification.new),
+                    modification.changed_fields.join(", ")
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum IdentityKey {
+    Assertion {
+        subject: CidBytes,
+        predicate: Option<CidBytes>,
+    },
+    Label(Vec<u8>),
+}
+
+/// Identity keys of the entity, with labels resolved among the entities of the same collection.
+fn identity_keys(entity: &Entity, entities: &BTreeMap<CidBytes, Entity>) -> Vec<IdentityKey> {
+    if let Some(assertion) = entity.as_assertion() {
+        return match assertion.subject() {
+            Some(subject) => vec![IdentityKey::Assertion {
+                subject: subject.clone(),
+                predicate: assertion.predicate().cloned(),
+            }],
+            None => Vec::new(),
+        };
+    }
+
+    let annotations = match entity.get_field("annotations") {
+        Some(FieldValue::Array(annotations)) => annotations,
+        _ => return Vec::new(),
+    };
+    annotations
+        .iter()
+        .filter_map(|cid| match entities.get(cid) {
+            Some(Entity::Annotation(annotation))
+                if annotation.property.as_slice() == vocab::RDFS_LABEL =>
+            {
+                Some(IdentityKey::Label(annotation.value.clone()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn changed_fields(old: &Entity, new: &Entity) -> Vec<&'static str> {
+    old.kind()
+        .schema()
+        .fields
+        .iter()
+        .map(|field| field.name)
+        .filter(|name| old.get_field(name) != new.get_field(name))
+        .collect()
+}
+
+fn by_cid<'a, I: IntoIterator<Item = &'a Entity>>(entities: I) -> BTreeMap<CidBytes, Entity> {
+    entities
+        .into_iter()
+        .map(|entity| (entity.to_bytes(), entity.clone()))
+        .collect()
+}
+
+/// Compares two entity collections, e.g. two releases of an ontology.
+pub fn diff<'a, 'b, I, J>(old: I, new: J) -> OntologyDiff
+where
+    I: IntoIterator<Item = &'a Entity>,
+    J: IntoIterator<Item = &'b Entity>,
+{
+    let old = by_cid(old);
+    let new = by_cid(new);
+
+    let mut removed: BTreeMap<&'static str, BTreeSet<&CidBytes>> = BTreeMap::new();
+    let mut added: BTreeMap<&'static str, BTreeSet<&CidBytes>> = BTreeMap::new();
+    for (cid, entity) in old.iter().filter(|(cid, _)| !new.contains_key(*cid)) {
+        removed.entry(entity.kind().into()).or_default().insert(cid);
+    }
+    for (cid, entity) in new.iter().filter(|(cid, _)| !old.contains_key(*cid)) {
+        added.entry(entity.kind().into()).or_default().insert(cid);
+    }
+
+    let mut kinds: BTreeSet<&'static str> = removed.keys().cloned().collect();
+    kinds.extend(added.keys().cloned());
+
+    let mut result = OntologyDiff::default();
+    for kind in kinds {
+        let mut kind_removed = removed.remove(kind).unwrap_or_default();
+        let mut kind_added = added.remove(kind).unwrap_or_default();
+
+        let mut added_by_key: BTreeMap<IdentityKey, BTreeSet<&CidBytes>> = BTreeMap::new();
+        let mut removed_by_key: BTreeMap<IdentityKey, BTreeSet<&CidBytes>> = BTreeMap::new();
+        for cid in kind_added.iter() {
+            for key in identity_keys(&new[*cid], &new) {
+                added_by_key.entry(key).or_default().insert(cid);
+            }
+        }
+        for cid in kind_removed.iter() {
+            for key in identity_keys(&old[*cid], &old) {
+                removed_by_key.entry(key).or_default().insert(cid);
+            }
+        }
+        // entities sharing a key with `keys`, in the other collection
+        let candidates = |keys: Vec<IdentityKey>,
+                          by_key: &BTreeMap<IdentityKey, BTreeSet<&CidBytes>>|
+         -> BTreeSet<CidBytes> {
+            keys.iter()
+                .filter_map(|key| by_key.get(key))
+                .flat_map(|cids| cids.iter().map(|cid| (*cid).clone()))
+                .collect()
+        };
+
+        let mut modified = Vec::new();
+        for old_cid in kind_removed.iter() {
+            let matches = candidates(identity_keys(&old[*old_cid], &old), &added_by_key);
+            if matches.len() != 1 {
+                continue;
+            }
+            let new_cid = matches.into_iter().next().unwrap();
+            let reverse = candidates(identity_keys(&new[&new_cid], &new), &removed_by_key);
+            if reverse.len() == 1 && reverse.contains(*old_cid) {
+                modified.push(Modification {
+                    old: (*old_cid).clone(),
+                    changed_fields: changed_fields(&old[*old_cid], &new[&new_cid]),
+                    new: new_cid,
+                });
+            }
+        }
+        for modification in modified.iter() {
+            kind_removed.remove(&modification.old);
+            kind_added.remove(&modification.new);
+        }
+
+        result.kinds.insert(
+            kind,
+            KindDiff {
+                added: kind_added.into_iter().cloned().collect(),
+                removed: kind_removed.into_iter().cloned().collect(),
+                modified,
+            },
+        );
+    }
+    result
+}
@@ -0,0 +1,133 @@
+//! Field-level edits of stored entities, with cascading rewrites of their dependents.
+//!
+//! Editing an entity changes its CID, so every entity referencing it has to be rewritten to
+//! reference the new CID, which in turn changes its CID. [`apply_edits`] finds all dependents
+//! via a [`BacklinkIndex`], rewrites them bottom-up, and replaces the old versions in the store
+//! with the new ones in one step.
+use crate::index::BacklinkIndex;
+use crate::ontology::{Canonicalize, Entity, FieldError, FieldValue};
+use crate::topo::{topo_sort_keyed, TopoError};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+type CidBytes = Vec<u8>;
+
+/// Changes to the fields of a single entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edit {
+    /// CID of the entity to edit.
+    pub cid: CidBytes,
+    /// New values of fields, by field name, applied in order.
+    pub changes: Vec<(String, FieldValue)>,
+}
+
+impl Edit {
+    pub fn new(cid: &[u8]) -> Self {
+        Edit {
+            cid: cid.to_vec(),
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn set(mut self, field: &str, value: FieldValue) -> Self {
+        self.changes.push((field.to_owned(), value));
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditError {
+    /// The edited entity is not in the store.
+    NotFound(CidBytes),
+    /// A change could not be applied to the edited entity.
+    Field { cid: CidBytes, error: FieldError },
+    /// The edits make the rewritten entities reference each other in a cycle.
+    Cycle(Vec<CidBytes>),
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EditError::NotFound(_) => write!(f, "edited entity not found"),
+            EditError::Field { error, .. } => match error {
+                FieldError::UnknownField(name) => write!(f, "unknown field \"{}\"", name),
+                FieldError::WrongShape { field } => {
+                    write!(f, "value does not have the shape of field \"{}\"", field)
+                }
+            },
+            EditError::Cycle(cids) => {
+                write!(f, "edits introduce a cycle between {} entities", cids.len())
+            }
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+/// Applies the edits to the entities in the store, and rewrites all entities that directly or
+/// transitively reference an edited entity.
+///
+/// Either all edits are applied or, on error, the store is left untouched. Replaced entities are
+/// removed from the store. Returns the mapping from old to new CIDs of all entities whose CID
+/// changed.
+pub fn apply_edits(
+    store: &mut BTreeMap<CidBytes, Entity>,
+    edits: &[Edit],
+) -> Result<BTreeMap<CidBytes, CidBytes>, EditError> {
+    let mut pending: BTreeMap<CidBytes, Entity> = BTreeMap::new();
+    for edit in edits {
+        let entity = match pending.get_mut(&edit.cid) {
+            Some(entity) => entity,
+            None => match store.get(&edit.cid) {
+                Some(entity) => pending.entry(edit.cid.clone()).or_insert(entity.clone()),
+                None => return Err(EditError::NotFound(edit.cid.clone())),
+            },
+        };
+        for (field, value) in edit.changes.iter() {
+            entity
+                .set_field(field, value.clone())
+                .map_err(|error| EditError::Field {
+                    cid: edit.cid.clone(),
+                    error,
+                })?;
+        }
+        entity.canonicalize();
+    }
+
+    let backlinks = BacklinkIndex::from_entities(store.values());
+    let mut affected: BTreeSet<CidBytes> = pending.keys().cloned().collect();
+    for cid in pending.keys() {
+        affected.extend(backlinks.dependents(cid));
+    }
+    for cid in affected.iter() {
+        if !pending.contains_key(cid) {
+            pending.insert(cid.clone(), store[cid].clone());
+        }
+    }
+
+    // bottom-up: an entity is rewritten after all affected entities it references
+    let order =
+        topo_sort_keyed(pending).map_err(|TopoError::Cycle(cids)| EditError::Cycle(cids))?;
+    let mut mapping: BTreeMap<CidBytes, CidBytes> = BTreeMap::new();
+    let mut rewritten: Vec<(CidBytes, Entity)> = Vec::new();
+    for (cid, mut entity) in order.entities {
+        entity.replace_references(&mapping);
+        let new_cid = entity.to_bytes();
+        if new_cid != cid {
+            mapping.insert(cid.clone(), new_cid);
+        }
+        rewritten.push((cid, entity));
+    }
+
+    // all old versions are removed before the new ones are inserted, as the new CID of one
+    // entity may be the old CID of another, e.g. when edits swap values
+    for old_cid in mapping.keys() {
+        store.remove(old_cid);
+    }
+    for (old_cid, entity) in rewritten {
+        if mapping.contains_key(&old_cid) {
+            store.insert(entity.to_bytes(), entity);
+        }
+    }
+    Ok(mapping)
+}
@@ -0,0 +1,244 @@
+use crate::ontology::{Entity, EntityKind, Polarity};
+use std::collections::{BTreeMap, BTreeSet};
+
+type CidBytes = Vec<u8>;
+
+/// Constraints for an assertion lookup. Unset constraints match every assertion.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssertionPattern {
+    pub subject: Option<CidBytes>,
+    /// Property of a property or annotation assertion.
+    pub property: Option<CidBytes>,
+    /// Class of a class assertion.
+    pub class: Option<CidBytes>,
+    /// Target or value of a property or annotation assertion.
+    pub object: Option<CidBytes>,
+    pub polarity: Option<Polarity>,
+}
+
+impl AssertionPattern {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subject(mut self, subject: &[u8]) -> Self {
+        self.subject = Some(subject.to_vec());
+        self
+    }
+
+    pub fn property(mut self, property: &[u8]) -> Self {
+        self.property = Some(property.to_vec());
+        self
+    }
+
+    pub fn class(mut self, class: &[u8]) -> Self {
+        self.class = Some(class.to_vec());
+        self
+    }
+
+    pub fn object(mut self, object: &[u8]) -> Self {
+        self.object = Some(object.to_vec());
+        self
+    }
+
+    pub fn polarity(mut self, polarity: Polarity) -> Self {
+        self.polarity = Some(polarity);
+        self
+    }
+}
+
+/// The values of an indexed assertion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedAssertion {
+    pub kind: EntityKind,
+    pub polarity: Polarity,
+    pub subject: Option<CidBytes>,
+    pub property: Option<CidBytes>,
+    pub class: Option<CidBytes>,
+    pub object: Option<CidBytes>,
+    /// Whether the object holds data instead of a CID (see `DataFieldNames`).
+    pub object_is_data: bool,
+}
+
+impl IndexedAssertion {
+    fn matches(&self, pattern: &AssertionPattern) -> bool {
+        fn matches_field(value: &Option<CidBytes>, constraint: &Option<CidBytes>) -> bool {
+            constraint.is_none() || value == constraint
+        }
+
+        matches_field(&self.subject, &pattern.subject)
+            && matches_field(&self.property, &pattern.property)
+            && matches_field(&self.class, &pattern.class)
+            && matches_field(&self.object, &pattern.object)
+            && (pattern.polarity.is_none() || pattern.polarity == Some(self.polarity))
+    }
+}
+
+/// Index of the assertions in an entity collection by subject, property, class and object.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssertionIndex {
+    assertions: BTreeMap<CidBytes, IndexedAssertion>,
+    by_subject: BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+    by_property: BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+    by_class: BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+    by_object: BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+}
+
+fn index_insert(
+    index: &mut BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+    key: &Option<CidBytes>,
+    cid: &[u8],
+) {
+    if let Some(key) = key {
+        index.entry(key.clone()).or_default().insert(cid.to_vec());
+    }
+}
+
+fn index_remove(
+    index: &mut BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+    key: &Option<CidBytes>,
+    cid: &[u8],
+) {
+    if let Some(key) = key {
+        let now_empty = match index.get_mut(key) {
+            Some(cids) => {
+                cids.remove(cid);
+                cids.is_empty()
+            }
+            None => false,
+        };
+        if now_empty {
+            index.remove(key);
+        }
+    }
+}
+
+impl AssertionIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indexes all assertions in `entities`, ignoring all other kinds.
+    pub fn from_entities<'a, I: IntoIterator<Item = &'a Entity>>(entities: I) -> Self {
+        let mut index = Self::new();
+        for entity in entities {
+            index.insert_entity(entity);
+        }
+        index
+    }
+
+    fn to_indexed(entity: &Entity) -> Option<IndexedAssertion> {
+        let assertion = entity.as_assertion()?;
+        let predicate = assertion.predicate().cloned();
+        let (property, class) = match entity.kind() {
+            EntityKind::ClassAssertion | EntityKind::NegativeClassAssertion => (None, predicate),
+            _ => (predicate, None),
+        };
+        Some(IndexedAssertion {
+            kind: entity.kind(),
+            polarity: entity.polarity(),
+            subject: assertion.subject().cloned(),
+            property,
+            class,
+            object: assertion.object().cloned(),
+            object_is_data: entity
+                .data_field_names()
+                .iter()
+                .any(|name| *name == "target" || *name == "value"),
+        })
+    }
+
+    /// Indexes the entity if it is an assertion, and returns its CID if it was.
+    pub fn insert_entity(&mut self, entity: &Entity) -> Option<CidBytes> {
+        let indexed = Self::to_indexed(entity)?;
+        let cid = entity.to_bytes();
+        if self.assertions.contains_key(&cid) {
+            return Some(cid);
+        }
+
+        index_insert(&mut self.by_subject, &indexed.subject, &cid);
+        index_insert(&mut self.by_property, &indexed.property, &cid);
+        index_insert(&mut self.by_class, &indexed.class, &cid);
+        index_insert(&mut self.by_object, &indexed.object, &cid);
+        self.assertions.insert(cid.clone(), indexed);
+        Some(cid)
+    }
+
+    /// Removes the assertion with the CID from the index. Returns whether it was indexed.
+    pub fn remove(&mut self, cid: &[u8]) -> bool {
+        let indexed = match self.assertions.remove(cid) {
+            Some(indexed) => indexed,
+            None => return false,
+        };
+        index_remove(&mut self.by_subject, &indexed.subject, cid);
+        index_remove(&mut self.by_property, &indexed.property, cid);
+        index_remove(&mut self.by_class, &indexed.class, cid);
+        index_remove(&mut self.by_object, &indexed.object, cid);
+        true
+    }
+
+    /// Returns the values of the indexed assertion with the CID.
+    pub fn get(&self, cid: &[u8]) -> Option<&IndexedAssertion> {
+        self.assertions.get(cid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.assertions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assertions.is_empty()
+    }
+
+    /// CIDs of all assertions that match the pattern, in ascending order.
+    pub fn query(&self, pattern: &AssertionPattern) -> Vec<CidBytes> {
+        // Start from the smallest candidate set of the indexed constraints, and check the
+        // remaining constraints on every candidate.
+        let mut smallest: Option<&BTreeSet<CidBytes>> = None;
+        let constraints = vec![
+            (&self.by_subject, &pattern.subject),
+            (&self.by_property, &pattern.property),
+            (&self.by_class, &pattern.class),
+            (&self.by_object, &pattern.object),
+        ];
+        for (index, key) in constraints {
+            let key = match key {
+                Some(key) => key,
+                None => continue,
+            };
+            let candidates = match index.get(key) {
+                Some(candidates) => candidates,
+                None => return vec![],
+            };
+            match smallest {
+                Some(current) if current.len() <= candidates.len() => {}
+                _ => smallest = Some(candidates),
+            }
+        }
+
+        match smallest {
+            Some(candidates) => candidates
+                .iter()
+                .filter(|cid| self.assertions[*cid].matches(pattern))
+                .cloned()
+                .collect(),
+            None => self
+                .assertions
+                .iter()
+                .filter(|(_, indexed)| indexed.matches(pattern))
+                .map(|(cid, _)| cid.clone())
+                .collect(),
+        }
+    }
+
+    /// Subjects of all positive class assertions of the class.
+    pub fn instances_of(&self, class: &[u8]) -> BTreeSet<CidBytes> {
+        let pattern = AssertionPattern::new()
+            .class(class)
+            .polarity(Polarity::Positive);
+        self.query(&pattern)
+            .iter()
+            .filter_map(|cid| self.assertions[cid].subject.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,131 @@
+use crate::ontology::{Entity, FieldValue};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+type CidBytes = Vec<u8>;
+
+/// A reference from the field of an entity to a CID.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Backlink {
+    /// CID of the referencing entity.
+    pub source: CidBytes,
+    /// Name of the field of the referencing entity, as found in its `cid_field_names`.
+    pub field: &'static str,
+}
+
+/// Reverse index from CIDs to the entities that reference them in their CID fields.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BacklinkIndex {
+    backlinks: BTreeMap<CidBytes, BTreeSet<Backlink>>,
+    /// (field, target) pairs of every indexed entity, needed for removal.
+    references: BTreeMap<CidBytes, Vec<(&'static str, CidBytes)>>,
+}
+
+/// Returns the CIDs referenced by the entity, together with the name of the field they are in.
+fn named_cid_fields(entity: &Entity) -> Vec<(&'static str, CidBytes)> {
+    let mut fields = Vec::new();
+    for name in entity.cid_field_names() {
+        let values = match entity.get_field(name) {
+            Some(FieldValue::Bytes(value)) => vec![value],
+            Some(FieldValue::OptionalBytes(value)) => value.into_iter().collect(),
+            Some(FieldValue::Array(values)) => values,
+            None => vec![],
+        };
+        fields.extend(values.into_iter().map(|value| (*name, value)));
+    }
+    fields
+}
+
+impl BacklinkIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_entities<'a, I: IntoIterator<Item = &'a Entity>>(entities: I) -> Self {
+        let mut index = Self::new();
+        for entity in entities {
+            index.insert_entity(entity);
+        }
+        index
+    }
+
+    /// Indexes the references of the entity, and returns its CID.
+    pub fn insert_entity(&mut self, entity: &Entity) -> CidBytes {
+        let cid = entity.to_bytes();
+        if self.references.contains_key(&cid) {
+            return cid;
+        }
+
+        let references = named_cid_fields(entity);
+        for (field, target) in references.iter() {
+            self.backlinks
+                .entry(target.clone())
+                .or_default()
+                .insert(Backlink {
+                    source: cid.clone(),
+                    field,
+                });
+        }
+        self.references.insert(cid.clone(), references);
+        cid
+    }
+
+    /// Removes the references of the entity with the CID. Returns whether it was indexed.
+    pub fn remove(&mut self, cid: &[u8]) -> bool {
+        let references = match self.references.remove(cid) {
+            Some(references) => references,
+            None => return false,
+        };
+        for (field, target) in references {
+            let backlink = Backlink {
+                source: cid.to_vec(),
+                field,
+            };
+            let now_empty = match self.backlinks.get_mut(&target) {
+                Some(backlinks) => {
+                    backlinks.remove(&backlink);
+                    backlinks.is_empty()
+                }
+                None => false,
+            };
+            if now_empty {
+                self.backlinks.remove(&target);
+            }
+        }
+        true
+    }
+
+    /// All references to the CID, ordered by source and field.
+    pub fn backlinks(&self, cid: &[u8]) -> Vec<Backlink> {
+        match self.backlinks.get(cid) {
+            Some(backlinks) => backlinks.iter().cloned().collect(),
+            None => vec![],
+        }
+    }
+
+    /// CIDs of the entities that directly reference the CID.
+    pub fn referencing(&self, cid: &[u8]) -> BTreeSet<CidBytes> {
+        match self.backlinks.get(cid) {
+            Some(backlinks) => backlinks
+                .iter()
+                .map(|backlink| backlink.source.clone())
+                .collect(),
+            None => BTreeSet::new(),
+        }
+    }
+
+    /// CIDs of all entities that directly or transitively reference the CID, not including the
+    /// CID itself.
+    pub fn dependents(&self, cid: &[u8]) -> BTreeSet<CidBytes> {
+        let mut dependents = BTreeSet::new();
+        let mut queue: VecDeque<CidBytes> = VecDeque::new();
+        queue.push_back(cid.to_vec());
+        while let Some(current) = queue.pop_front() {
+            for source in self.referencing(&current) {
+                if source.as_slice() != cid && dependents.insert(source.clone()) {
+                    queue.push_back(source);
+                }
+            }
+        }
+        dependents
+    }
+}
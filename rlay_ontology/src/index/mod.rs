@@ -0,0 +1,6 @@
+//! In-memory indexes over collections of entities, that can be updated incrementally.
+mod assertions;
+mod backlinks;
+
+pub use self::assertions::{AssertionIndex, AssertionPattern, IndexedAssertion};
+pub use self::backlinks::{Backlink, BacklinkIndex};
@@ -0,0 +1,98 @@
+//! Lookup of `rdfs:label` annotations in a preferred language.
+//!
+//! The `value` of a label annotation is always the raw UTF-8 text. A language-tagged label, as
+//! created by [`vocab::label_in`](../vocab/fn.label_in.html), carries its language tag in a nested
+//! `dcterms:language` annotation.
+use crate::ontology::{Annotation, Entity, EntityResolver};
+use crate::vocab;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Label {
+    pub text: String,
+    /// Lowercase language tag, if the label is language-tagged.
+    pub language: Option<String>,
+}
+
+fn resolve_annotation<R: EntityResolver + ?Sized>(cid: &[u8], resolver: &R) -> Option<Annotation> {
+    match resolver.resolve(cid) {
+        Some(Entity::Annotation(annotation)) => Some(annotation),
+        _ => None,
+    }
+}
+
+/// The label stated by the annotation, if it is a label.
+///
+/// Returns `None` if the text is not valid UTF-8, or if a nested annotation can't be resolved, as
+/// the language of the label would be unknown.
+fn to_label<R: EntityResolver + ?Sized>(annotation: Annotation, resolver: &R) -> Option<Label> {
+    if annotation.property.as_slice() != vocab::RDFS_LABEL {
+        return None;
+    }
+    let mut language = None;
+    for cid in annotation.annotations.iter() {
+        let nested = resolve_annotation(cid, resolver)?;
+        if nested.property.as_slice() == vocab::DCTERMS_LANGUAGE {
+            language = Some(String::from_utf8(nested.value).ok()?);
+        }
+    }
+    Some(Label {
+        text: String::from_utf8(annotation.value).ok()?,
+        language,
+    })
+}
+
+/// All labels among the annotations, identified by their CIDs, in the order of the annotations.
+///
+/// Annotations that can't be resolved, that are not labels, or whose text or language can't be
+/// read are skipped.
+pub fn labels<R: EntityResolver + ?Sized>(annotations: &[Vec<u8>], resolver: &R) -> Vec<Label> {
+    annotations
+        .iter()
+        .filter_map(|cid| resolve_annotation(cid, resolver))
+        .filter_map(|annotation| to_label(annotation, resolver))
+        .collect()
+}
+
+/// Picks the label that best matches the preferred languages, in order of preference.
+///
+/// For each preferred language, a label with exactly that tag is chosen first, then one with a
+/// more general tag (`en` for `en-gb`), then one with a more specific tag (`en-gb` for `en`).
+/// Without a match, an untagged label is chosen, and finally any label. Ties are broken by the
+/// order of the annotations.
+pub fn best_label<R: EntityResolver + ?Sized>(
+    annotations: &[Vec<u8>],
+    resolver: &R,
+    preferred: &[&str],
+) -> Option<Label> {
+    let labels = labels(annotations, resolver);
+    let with_language = |predicate: &dyn Fn(&str) -> bool| {
+        labels.iter().find(|label| match &label.language {
+            Some(language) => predicate(language),
+            None => false,
+        })
+    };
+
+    for preferred in preferred {
+        let preferred = preferred.to_lowercase();
+        if let Some(label) = with_language(&|language| language == preferred) {
+            return Some(label.clone());
+        }
+        let mut general = preferred.as_str();
+        while let Some(pos) = general.rfind('-') {
+            general = &general[..pos];
+            if let Some(label) = with_language(&|language| language == general) {
+                return Some(label.clone());
+            }
+        }
+        let prefix = format!("{}-", preferred);
+        if let Some(label) = with_language(&|language| language.starts_with(&prefix)) {
+            return Some(label.clone());
+        }
+    }
+
+    labels
+        .iter()
+        .find(|label| label.language.is_none())
+        .or_else(|| labels.first())
+        .cloned()
+}
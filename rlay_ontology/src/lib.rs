@@ -13,8 +13,36 @@ use cid_fork_rlay::{Cid, Codec, Error as CidError, Version};
 #[cfg(feature = "std")]
 use integer_encoding::VarIntReader;
 
+#[cfg(feature = "std")]
+pub mod consistency;
+#[cfg(feature = "std")]
+pub mod diff;
+#[cfg(feature = "std")]
+pub mod edit;
+#[cfg(feature = "std")]
+pub mod index;
+#[cfg(feature = "std")]
+pub mod label;
+#[cfg(feature = "std")]
+pub mod literal;
+#[cfg(feature = "std")]
+pub mod migrate;
 pub mod ontology;
+#[cfg(feature = "std")]
+pub mod query;
+#[cfg(feature = "reasoner")]
+pub mod reasoner;
+#[cfg(feature = "std")]
+pub mod store;
+#[cfg(feature = "std")]
+pub mod taxonomy;
+#[cfg(feature = "std")]
+pub mod topo;
+#[cfg(feature = "std")]
+pub mod vocab;
 pub mod prelude {
+    #[cfg(feature = "std")]
+    pub use crate::ontology::builder::*;
     #[cfg(feature = "serde")]
     pub use crate::ontology::compact::*;
     #[cfg(feature = "std")]
@@ -0,0 +1,386 @@
+//! Typed values of `Literal` entities.
+//!
+//! The `datatype` of a literal is the CID of one of the well-known datatypes in
+//! [`vocab`](../vocab/index.html), and the `value` holds the canonical encoding of the value:
+//!
+//! | Variant      | Datatype           | Encoding of `value`                                       |
+//! |--------------|--------------------|-----------------------------------------------------------|
+//! | `String`     | `xsd:string`       | UTF-8                                                     |
+//! | `LangString` | `rdf:langString`   | UTF-8, with the lowercase tag in `language`               |
+//! | `Integer`    | `xsd:integer`      | decimal digits, `-` for negative numbers, no leading `0`  |
+//! | `Decimal`    | `xsd:decimal`      | canonical XSD lexical form, e.g. `-1.5` or `3.0`          |
+//! | `Boolean`    | `xsd:boolean`      | `true` or `false`                                         |
+//! | `DateTime`   | `xsd:dateTime`     | XSD lexical form with `Z` for UTC, `2019-04-01T12:00:00Z` |
+//! | `Bytes`      | `xsd:base64Binary` | the raw bytes                                             |
+//!
+//! Only canonical encodings are accepted when decoding, so that every value has exactly one CID.
+//! Only `LangString` literals have a `language`. Literals without a datatype are rejected, as
+//! they would give the same value a second CID.
+//!
+//! Data fields, like the `target` of a `DataPropertyAssertion`, hold a typed value inline: the v0
+//! encoding of its `Literal`, which carries the datatype and language tag along with the value.
+//! They never reference a `Literal` by its CID, as data fields are not followed by the tools that
+//! walk the CID fields of a graph.
+use crate::ontology::v0::EntityV0;
+use crate::ontology::Literal;
+use crate::vocab;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiteralValue {
+    String(String),
+    LangString {
+        text: String,
+        lang: String,
+    },
+    /// Canonical lexical form of the integer, which may have any number of digits.
+    Integer(String),
+    /// Canonical lexical form of the decimal.
+    Decimal(String),
+    Boolean(bool),
+    /// Lexical form of the date and time.
+    DateTime(String),
+    Bytes(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiteralError {
+    /// The literal has no datatype.
+    MissingDatatype,
+    /// The datatype is not one of the well-known datatypes.
+    UnknownDatatype(Vec<u8>),
+    /// The value is not the canonical encoding of a value of the datatype.
+    InvalidValue {
+        datatype: &'static str,
+        value: Vec<u8>,
+    },
+    /// The language tag is missing from an `rdf:langString`, is not a lowercase language tag, or
+    /// is given for another datatype.
+    InvalidLanguage {
+        datatype: &'static str,
+        language: Option<Vec<u8>>,
+    },
+    /// The data value is not the canonical v0 encoding of a `Literal`.
+    InvalidData(Vec<u8>),
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LiteralError::MissingDatatype => write!(f, "missing datatype"),
+            LiteralError::UnknownDatatype(_) => write!(f, "unknown datatype"),
+            LiteralError::InvalidValue { datatype, value } => write!(
+                f,
+                "{:?} is not a canonical value of <{}>",
+                String::from_utf8_lossy(value),
+                datatype
+            ),
+            LiteralError::InvalidLanguage { datatype, language } => match language {
+                Some(language) => write!(
+                    f,
+                    "{:?} is not a valid language tag for <{}>",
+                    String::from_utf8_lossy(language),
+                    datatype
+                ),
+                None => write!(f, "missing language tag for <{}>", datatype),
+            },
+            LiteralError::InvalidData(_) => write!(f, "data value is not an encoded literal"),
+        }
+    }
+}
+
+impl std::error::Error for LiteralError {}
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_canonical_integer(s: &str) -> bool {
+    let digits = match s.starts_with('-') {
+        true => &s[1..],
+        false => s,
+    };
+    is_digits(digits) && (digits == "0" || !digits.starts_with('0')) && s != "-0"
+}
+
+fn is_canonical_decimal(s: &str) -> bool {
+    let (int, frac) = match s.find('.') {
+        Some(pos) => (&s[..pos], &s[pos + 1..]),
+        None => return false,
+    };
+    let unsigned = match int.starts_with('-') {
+        true => &int[1..],
+        false => int,
+    };
+    let canonical_frac = frac == "0" || (is_digits(frac) && !frac.ends_with('0'));
+    let canonical_int = is_digits(unsigned) && (unsigned == "0" || !unsigned.starts_with('0'));
+    canonical_int && canonical_frac && s != "-0.0"
+}
+
+fn is_lang_tag(s: &str) -> bool {
+    !s.is_empty()
+        && s.split('-').all(|part| {
+            !part.is_empty()
+                && part.len() <= 8
+                && part
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        })
+}
+
+/// Checks `YYYY-MM-DDThh:mm:ss[.s+][Z|(+|-)hh:mm]`, with `Z` instead of `+00:00` and without
+/// trailing zeros in the fractional seconds.
+fn is_canonical_date_time(s: &str) -> bool {
+    let (year, rest) = match s.find("-") {
+        Some(0) | None => return false,
+        Some(pos) => (&s[..pos], &s[pos..]),
+    };
+    if !is_digits(year) || year.len() < 4 || (year.len() > 4 && year.starts_with('0')) {
+        return false;
+    }
+    let bytes = rest.as_bytes();
+    let pattern = b"-00-00T00:00:00";
+    if bytes.len() < pattern.len() {
+        return false;
+    }
+    for (b, p) in bytes.iter().zip(pattern.iter()) {
+        let matches = match p {
+            b'0' => b.is_ascii_digit(),
+            _ => b == p,
+        };
+        if !matches {
+            return false;
+        }
+    }
+    let field = |start: usize| -> u32 { rest[start..start + 2].parse().unwrap() };
+    let (month, day, hour, minute, second) = (field(1), field(4), field(7), field(10), field(13));
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return false;
+    }
+
+    let mut rest = &rest[pattern.len()..];
+    if rest.starts_with('.') {
+        let end = rest[1..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map(|pos| pos + 1)
+            .unwrap_or_else(|| rest.len());
+        let fraction = &rest[1..end];
+        if !is_digits(fraction) || fraction.ends_with('0') {
+            return false;
+        }
+        rest = &rest[end..];
+    }
+    match rest {
+        "" | "Z" => true,
+        "+00:00" | "-00:00" => false,
+        _ => {
+            let bytes = rest.as_bytes();
+            rest.is_ascii()
+                && bytes.len() == 6
+                && (bytes[0] == b'+' || bytes[0] == b'-')
+                && is_digits(&rest[1..3])
+                && bytes[3] == b':'
+                && is_digits(&rest[4..6])
+                && rest[1..3].parse::<u32>().unwrap() <= 14
+                && rest[4..6].parse::<u32>().unwrap() <= 59
+        }
+    }
+}
+
+impl LiteralValue {
+    /// IRI of the datatype of the value.
+    pub fn datatype_iri(&self) -> &'static str {
+        match self {
+            LiteralValue::String(_) => vocab::XSD_STRING_IRI,
+            LiteralValue::LangString { .. } => vocab::RDF_LANG_STRING_IRI,
+            LiteralValue::Integer(_) => vocab::XSD_INTEGER_IRI,
+            LiteralValue::Decimal(_) => vocab::XSD_DECIMAL_IRI,
+            LiteralValue::Boolean(_) => vocab::XSD_BOOLEAN_IRI,
+            LiteralValue::DateTime(_) => vocab::XSD_DATE_TIME_IRI,
+            LiteralValue::Bytes(_) => vocab::XSD_BASE64_BINARY_IRI,
+        }
+    }
+
+    /// CID of the datatype of the value.
+    pub fn datatype(&self) -> &'static [u8] {
+        match self {
+            LiteralValue::String(_) => vocab::XSD_STRING,
+            LiteralValue::LangString { .. } => vocab::RDF_LANG_STRING,
+            LiteralValue::Integer(_) => vocab::XSD_INTEGER,
+            LiteralValue::Decimal(_) => vocab::XSD_DECIMAL,
+            LiteralValue::Boolean(_) => vocab::XSD_BOOLEAN,
+            LiteralValue::DateTime(_) => vocab::XSD_DATE_TIME,
+            LiteralValue::Bytes(_) => vocab::XSD_BASE64_BINARY,
+        }
+    }
+
+    /// Language tag of a `LangString`.
+    pub fn language(&self) -> Option<&str> {
+        match self {
+            LiteralValue::LangString { lang, .. } => Some(lang),
+            _ => None,
+        }
+    }
+
+    /// Canonical encoding of the value, without the language tag.
+    ///
+    /// Fails for an `Integer`, `Decimal` or `DateTime` that is not in its canonical lexical form.
+    pub fn encode(&self) -> Result<Vec<u8>, LiteralError> {
+        let encoded = match self {
+            LiteralValue::String(text) => text.as_bytes().to_vec(),
+            LiteralValue::LangString { text, .. } => text.as_bytes().to_vec(),
+            LiteralValue::Integer(value) => value.as_bytes().to_vec(),
+            LiteralValue::Decimal(value) => value.as_bytes().to_vec(),
+            LiteralValue::Boolean(value) => value.to_string().into_bytes(),
+            LiteralValue::DateTime(value) => value.as_bytes().to_vec(),
+            LiteralValue::Bytes(bytes) => bytes.clone(),
+        };
+        let valid = match self {
+            LiteralValue::Integer(value) => is_canonical_integer(value),
+            LiteralValue::Decimal(value) => is_canonical_decimal(value),
+            LiteralValue::DateTime(value) => is_canonical_date_time(value),
+            _ => true,
+        };
+        match valid {
+            true => Ok(encoded),
+            false => Err(LiteralError::InvalidValue {
+                datatype: self.datatype_iri(),
+                value: encoded,
+            }),
+        }
+    }
+
+    /// Decodes the canonical encoding of a value of the datatype, identified by its CID, and its
+    /// language tag.
+    pub fn decode(
+        datatype: &[u8],
+        value: &[u8],
+        language: Option<&[u8]>,
+    ) -> Result<Self, LiteralError> {
+        let invalid = |datatype: &'static str| LiteralError::InvalidValue {
+            datatype,
+            value: value.to_vec(),
+        };
+
+        let iri = match datatype {
+            d if d == vocab::XSD_BASE64_BINARY => vocab::XSD_BASE64_BINARY_IRI,
+            d if d == vocab::XSD_STRING => vocab::XSD_STRING_IRI,
+            d if d == vocab::RDF_LANG_STRING => vocab::RDF_LANG_STRING_IRI,
+            d if d == vocab::XSD_INTEGER => vocab::XSD_INTEGER_IRI,
+            d if d == vocab::XSD_DECIMAL => vocab::XSD_DECIMAL_IRI,
+            d if d == vocab::XSD_BOOLEAN => vocab::XSD_BOOLEAN_IRI,
+            d if d == vocab::XSD_DATE_TIME => vocab::XSD_DATE_TIME_IRI,
+            _ => return Err(LiteralError::UnknownDatatype(datatype.to_vec())),
+        };
+        let lang = language
+            .and_then(|language| std::str::from_utf8(language).ok())
+            .filter(|language| is_lang_tag(language));
+        let valid_language = match iri {
+            vocab::RDF_LANG_STRING_IRI => lang.is_some(),
+            _ => language.is_none(),
+        };
+        if !valid_language {
+            return Err(LiteralError::InvalidLanguage {
+                datatype: iri,
+                language: language.map(|language| language.to_vec()),
+            });
+        }
+        if iri == vocab::XSD_BASE64_BINARY_IRI {
+            return Ok(LiteralValue::Bytes(value.to_vec()));
+        }
+
+        let text = std::str::from_utf8(value).map_err(|_| invalid(iri))?;
+
+        let decoded = match iri {
+            vocab::XSD_STRING_IRI => Some(LiteralValue::String(text.to_owned())),
+            vocab::RDF_LANG_STRING_IRI => Some(LiteralValue::LangString {
+                text: text.to_owned(),
+                lang: lang.unwrap().to_owned(),
+            }),
+            vocab::XSD_INTEGER_IRI => match is_canonical_integer(text) {
+                true => Some(LiteralValue::Integer(text.to_owned())),
+                false => None,
+            },
+            vocab::XSD_DECIMAL_IRI => match is_canonical_decimal(text) {
+                true => Some(LiteralValue::Decimal(text.to_owned())),
+                false => None,
+            },
+            vocab::XSD_BOOLEAN_IRI => match text {
+                "true" => Some(LiteralValue::Boolean(true)),
+                "false" => Some(LiteralValue::Boolean(false)),
+                _ => None,
+            },
+            _ => match is_canonical_date_time(text) {
+                true => Some(LiteralValue::DateTime(text.to_owned())),
+                false => None,
+            },
+        };
+        decoded.ok_or_else(|| invalid(iri))
+    }
+
+    pub fn to_literal(&self) -> Result<Literal, LiteralError> {
+        if let Some(lang) = self.language() {
+            if !is_lang_tag(lang) {
+                return Err(LiteralError::InvalidLanguage {
+                    datatype: self.datatype_iri(),
+                    language: Some(lang.as_bytes().to_vec()),
+                });
+            }
+        }
+        Ok(Literal {
+            datatype: Some(self.datatype().to_vec()),
+            value: self.encode()?,
+            language: self.language().map(|lang| lang.as_bytes().to_vec()),
+        })
+    }
+
+    pub fn from_literal(literal: &Literal) -> Result<Self, LiteralError> {
+        let datatype = literal
+            .datatype
+            .as_deref()
+            .ok_or(LiteralError::MissingDatatype)?;
+        Self::decode(datatype, &literal.value, literal.language.as_deref())
+    }
+
+    /// Inline encoding of the value for a data field: the v0 encoding of its `Literal`.
+    pub fn to_data(&self) -> Result<Vec<u8>, LiteralError> {
+        let mut bytes = Vec::new();
+        EntityV0::Literal(self.to_literal()?)
+            .serialize(&mut bytes)
+            .unwrap();
+        Ok(bytes)
+    }
+
+    /// Decodes the value of a data field, e.g. the `target` of a `DataPropertyAssertion`.
+    pub fn from_data(bytes: &[u8]) -> Result<Self, LiteralError> {
+        match EntityV0::deserialize_strict(bytes) {
+            Ok(EntityV0::Literal(literal)) => Self::from_literal(&literal),
+            _ => Err(LiteralError::InvalidData(bytes.to_vec())),
+        }
+    }
+}
+
+impl fmt::Display for LiteralValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LiteralValue::String(text) => write!(f, "{:?}", text),
+            LiteralValue::LangString { text, lang } => write!(f, "{:?}@{}", text, lang),
+            LiteralValue::Integer(value) => write!(f, "{}", value),
+            LiteralValue::Decimal(value) => write!(f, "{}", value),
+            LiteralValue::Boolean(value) => write!(f, "{}", value),
+            LiteralValue::DateTime(value) => write!(f, "{}", value),
+            LiteralValue::Bytes(bytes) => {
+                write!(f, "0x")?;
+                for byte in bytes {
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Migration of an entity graph to a different CID scheme.
+//!
+//! Changing the hash function, the codec codes or the canonicalization changes every CID, and
+//! with it every reference between entities. [`migrate`] recomputes the CIDs under the new
+//! [`CidScheme`] in dependency order, so that each entity is rewritten to reference the new CIDs
+//! of the entities it depends on before its own CID is computed.
+use crate::ontology::Entity;
+use crate::topo::{topo_sort_keyed, TopoError};
+use cid_fork_rlay::{Cid, Codec, Version};
+use multibase::Base;
+use multihash::Hash;
+use std::collections::BTreeMap;
+use std::fmt;
+
+type CidBytes = Vec<u8>;
+
+/// A way of computing the CID of an entity.
+pub trait CidScheme {
+    fn cid_bytes(&self, entity: &Entity) -> CidBytes;
+}
+
+/// Hashes the canonical encoding of the entity with the hash function, and uses the kind id
+/// offset by `codec_offset` as the codec code.
+#[derive(Debug, Clone)]
+pub struct HashScheme {
+    pub hash: Hash,
+    pub codec_offset: u64,
+}
+
+impl HashScheme {
+    /// The scheme CIDs are currently computed with by `ToCid`.
+    pub fn current() -> Self {
+        HashScheme {
+            hash: Hash::Keccak256,
+            codec_offset: 0xc000,
+        }
+    }
+}
+
+impl CidScheme for HashScheme {
+    fn cid_bytes(&self, entity: &Entity) -> CidBytes {
+        let hashed = multihash::encode(self.hash, &entity.canonical_encoding()).unwrap();
+        let codec = Codec::Unknown(self.codec_offset + entity.kind().id());
+        Cid::new(codec, Version::V1, &hashed).to_bytes()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationError {
+    /// The entities reference each other in a cycle, so there is no dependency order.
+    Cycle(Vec<CidBytes>),
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MigrationError::Cycle(cids) => {
+                write!(f, "cyclic references between {} entities", cids.len())
+            }
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Migration {
+    /// The rewritten entities, in dependency order.
+    pub entities: Vec<Entity>,
+    /// Mapping from the CIDs of the entities under the old scheme to those under the new one.
+    pub mapping: BTreeMap<CidBytes, CidBytes>,
+}
+
+/// The mapping table, one line per entity with the old and the new CID as base58btc-encoded
+/// multibase strings, separated by a tab.
+impl fmt::Display for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (old, new) in self.mapping.iter() {
+            writeln!(
+                f,
+                "{}\t{}",
+                multibase::encode(Base::Base58btc, old),
+                multibase::encode(Base::Base58btc, new)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Recomputes the CIDs of the entities, which reference each other by their CIDs under the `from`
+/// scheme, under the `to` scheme, rewriting all references between them. References to CIDs
+/// outside of the entities are left unchanged.
+pub fn migrate<'a, I, F, T>(entities: I, from: &F, to: &T) -> Result<Migration, MigrationError>
+where
+    I: IntoIterator<Item = &'a Entity>,
+    F: CidScheme + ?Sized,
+    T: CidScheme + ?Sized,
+{
+    let entities: BTreeMap<CidBytes, Entity> = entities
+        .into_iter()
+        .map(|entity| (from.cid_bytes(entity), entity.clone()))
+        .collect();
+
+    let mut migration = Migration::default();
+    let order =
+        topo_sort_keyed(entities).map_err(|TopoError::Cycle(cids)| MigrationError::Cycle(cids))?;
+    for (old_cid, mut entity) in order.entities {
+        entity.replace_references(&migration.mapping);
+        migration.mapping.insert(old_cid, to.cid_bytes(&entity));
+        migration.entities.push(entity);
+    }
+    Ok(migration)
+}
@@ -0,0 +1,292 @@
+//! Strict decoding for the compact and v0 formats.
+//!
+//! The lenient decoders accept any CBOR that `serde_cbor` is able to parse, which means that the
+//! same logical entity may arrive in many different byte representations. The functions in this
+//! module only accept the exact bytes the serializer would produce, and report the canonicality
+//! rule that was violated otherwise.
+use integer_encoding::VarInt;
+use std::fmt;
+
+/// A violated canonicality rule. Offsets are relative to the start of the CBOR payload.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CanonicalError {
+    /// The input ended in the middle of a data item.
+    Truncated,
+    /// An array, map, byte string or text string was encoded with indefinite length.
+    IndefiniteLength { offset: usize },
+    /// An integer or length was not encoded in its shortest form.
+    NonMinimalInteger { offset: usize },
+    /// A varint in the v0 header was not encoded in its shortest form.
+    NonMinimalVarint,
+    /// A data item the serializer never produces (tags, floats, nested maps, ...).
+    UnexpectedType { offset: usize },
+    /// A map key was not a text string.
+    NonStringKey { offset: usize },
+    /// A map key that is not a field of the entity kind.
+    UnknownField { offset: usize, key: String },
+    /// A map key appeared more than once.
+    DuplicateKey { offset: usize, key: String },
+    /// Map keys did not appear in field declaration order.
+    UnsortedKeys { offset: usize, key: String },
+    /// An omittable field was present with an explicit `null`.
+    ExplicitNull { offset: usize, key: String },
+    /// An omittable array field was present as an empty array.
+    EmptyArray { offset: usize, key: String },
+    /// Additional bytes followed the entity.
+    TrailingBytes { offset: usize },
+    /// The v0 version number is not supported.
+    UnsupportedVersion(u64),
+    /// The v0 kind id does not belong to any entity kind.
+    UnknownKind(u64),
+    /// The payload could not be decoded into the entity kind.
+    Malformed(String),
+    /// The payload decoded, but re-encoding it produces different bytes.
+    NotCanonical,
+}
+
+impl fmt::Display for CanonicalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CanonicalError::Truncated => write!(f, "unexpected end of input"),
+            CanonicalError::IndefiniteLength { offset } => {
+                write!(f, "indefinite-length item at offset {}", offset)
+            }
+            CanonicalError::NonMinimalInteger { offset } => {
+                write!(f, "non-minimal integer encoding at offset {}", offset)
+            }
+            CanonicalError::NonMinimalVarint => write!(f, "non-minimal varint in header"),
+            CanonicalError::UnexpectedType { offset } => {
+                write!(f, "unexpected data item at offset {}", offset)
+            }
+            CanonicalError::NonStringKey { offset } => {
+                write!(f, "non-string map key at offset {}", offset)
+            }
+            CanonicalError::UnknownField { offset, key } => {
+                write!(f, "unknown field \"{}\" at offset {}", key, offset)
+            }
+            CanonicalError::DuplicateKey { offset, key } => {
+                write!(f, "duplicate field \"{}\" at offset {}", key, offset)
+            }
+            CanonicalError::UnsortedKeys { offset, key } => {
+                write!(f, "field \"{}\" out of order at offset {}", key, offset)
+            }
+            CanonicalError::ExplicitNull { offset, key } => {
+                write!(
+                    f,
+                    "explicit null for field \"{}\" at offset {}",
+                    key, offset
+                )
+            }
+            CanonicalError::EmptyArray { offset, key } => {
+                write!(
+                    f,
+                    "explicit empty array for field \"{}\" at offset {}",
+                    key, offset
+                )
+            }
+            CanonicalError::TrailingBytes { offset } => {
+                write!(f, "trailing bytes at offset {}", offset)
+            }
+            CanonicalError::UnsupportedVersion(version) => {
+                write!(f, "unsupported entity version {}", version)
+            }
+            CanonicalError::UnknownKind(kind_id) => write!(f, "unknown kind id {}", kind_id),
+            CanonicalError::Malformed(msg) => write!(f, "malformed entity: {}", msg),
+            CanonicalError::NotCanonical => {
+                write!(f, "entity does not match its canonical encoding")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CanonicalError {}
+
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_SIMPLE: u8 = 7;
+
+const SIMPLE_NULL: u8 = 22;
+
+/// Head of a CBOR data item.
+struct Header {
+    major: u8,
+    additional: u8,
+    value: u64,
+    /// Offset of the first byte after the head.
+    end: usize,
+}
+
+fn read_header(bytes: &[u8], offset: usize) -> Result<Header, CanonicalError> {
+    let initial = *bytes.get(offset).ok_or(CanonicalError::Truncated)?;
+    let major = initial >> 5;
+    let additional = initial & 0x1f;
+
+    let (len, min) = match additional {
+        0..=23 => {
+            return Ok(Header {
+                major,
+                additional,
+                value: u64::from(additional),
+                end: offset + 1,
+            })
+        }
+        24 => (1, 24),
+        25 => (2, 0x100),
+        26 => (4, 0x1_0000),
+        27 => (8, 0x1_0000_0000),
+        31 => return Err(CanonicalError::IndefiniteLength { offset }),
+        _ => return Err(CanonicalError::UnexpectedType { offset }),
+    };
+
+    let start = offset + 1;
+    let value_bytes = bytes
+        .get(start..start + len)
+        .ok_or(CanonicalError::Truncated)?;
+    let value = value_bytes
+        .iter()
+        .fold(0u64, |acc, byte| (acc << 8) | u64::from(*byte));
+    // Floats share the head layout with integers, but are never produced by the serializer.
+    if major == MAJOR_SIMPLE {
+        return Err(CanonicalError::UnexpectedType { offset });
+    }
+    if value < min {
+        return Err(CanonicalError::NonMinimalInteger { offset });
+    }
+
+    Ok(Header {
+        major,
+        additional,
+        value,
+        end: start + len,
+    })
+}
+
+/// Checks a single (non-map) data item and returns the offset of the first byte after it.
+fn check_item(bytes: &[u8], offset: usize) -> Result<usize, CanonicalError> {
+    let header = read_header(bytes, offset)?;
+    match header.major {
+        0 | 1 => Ok(header.end),
+        MAJOR_BYTES | MAJOR_TEXT => {
+            let end = header.end + header.value as usize;
+            if end > bytes.len() {
+                return Err(CanonicalError::Truncated);
+            }
+            Ok(end)
+        }
+        MAJOR_ARRAY => {
+            let mut pos = header.end;
+            for _ in 0..header.value {
+                pos = check_item(bytes, pos)?;
+            }
+            Ok(pos)
+        }
+        _ => Err(CanonicalError::UnexpectedType { offset }),
+    }
+}
+
+/// Checks that `bytes` hold a single map whose keys are a strictly ordered subset of
+/// `field_keys`, without any non-canonical encodings. `array_keys` are the keys of the array
+/// fields, which are omitted instead of being encoded as empty arrays.
+pub fn check_canonical_map(
+    bytes: &[u8],
+    field_keys: &[&str],
+    array_keys: &[&str],
+) -> Result<(), CanonicalError> {
+    let header = read_header(bytes, 0)?;
+    if header.major != MAJOR_MAP {
+        return Err(CanonicalError::UnexpectedType { offset: 0 });
+    }
+
+    let mut pos = header.end;
+    let mut last_index: Option<usize> = None;
+    for _ in 0..header.value {
+        let key_offset = pos;
+        let key_header = read_header(bytes, key_offset)?;
+        if key_header.major != MAJOR_TEXT {
+            return Err(CanonicalError::NonStringKey { offset: key_offset });
+        }
+        pos = check_item(bytes, key_offset)?;
+        let key = String::from_utf8_lossy(&bytes[key_header.end..pos]).into_owned();
+
+        let index = match field_keys.iter().position(|n| *n == key) {
+            Some(index) => index,
+            None => {
+                return Err(CanonicalError::UnknownField {
+                    offset: key_offset,
+                    key,
+                })
+            }
+        };
+        match last_index {
+            Some(last) if last == index => {
+                return Err(CanonicalError::DuplicateKey {
+                    offset: key_offset,
+                    key,
+                })
+            }
+            Some(last) if last > index => {
+                return Err(CanonicalError::UnsortedKeys {
+                    offset: key_offset,
+                    key,
+                })
+            }
+            _ => last_index = Some(index),
+        }
+
+        let value_header = read_header(bytes, pos)?;
+        if value_header.major == MAJOR_SIMPLE && value_header.additional == SIMPLE_NULL {
+            return Err(CanonicalError::ExplicitNull { offset: pos, key });
+        }
+        if value_header.major == MAJOR_ARRAY
+            && value_header.value == 0
+            && array_keys.contains(&key.as_str())
+        {
+            return Err(CanonicalError::EmptyArray { offset: pos, key });
+        }
+        pos = check_item(bytes, pos)?;
+    }
+
+    if pos != bytes.len() {
+        return Err(CanonicalError::TrailingBytes { offset: pos });
+    }
+    Ok(())
+}
+
+/// Decodes a compact-format entity, only accepting the exact bytes the serializer would produce.
+pub fn decode_compact_strict<T>(
+    bytes: &[u8],
+    field_keys: &[&str],
+    array_keys: &[&str],
+) -> Result<T, CanonicalError>
+where
+    T: serde::de::DeserializeOwned + serde::Serialize,
+{
+    check_canonical_map(bytes, field_keys, array_keys)?;
+
+    let decoded: T =
+        serde_cbor::from_slice(bytes).map_err(|err| CanonicalError::Malformed(err.to_string()))?;
+    let reencoded =
+        serde_cbor::to_vec(&decoded).map_err(|err| CanonicalError::Malformed(err.to_string()))?;
+    if reencoded != bytes {
+        return Err(CanonicalError::NotCanonical);
+    }
+
+    Ok(decoded)
+}
+
+/// Reads a varint from the start of `bytes`, rejecting non-minimal encodings.
+///
+/// Returns the value and the number of bytes consumed.
+pub fn read_varint_strict(bytes: &[u8]) -> Result<(u64, usize), CanonicalError> {
+    let (value, read) = u64::decode_var(bytes);
+    // the last byte of a complete varint has its continuation bit unset
+    if read == 0 || bytes[read - 1] & 0x80 != 0 {
+        return Err(CanonicalError::Truncated);
+    }
+    if read != value.required_space() {
+        return Err(CanonicalError::NonMinimalVarint);
+    }
+    Ok((value, read))
+}
@@ -0,0 +1,292 @@
+//! Declarative construction of small entity graphs via the [`rlay_graph!`] macro.
+//!
+//! ```ignore
+//! let graph = rlay_graph! {
+//!     label = Annotation { property: rdfs_label, value: "Organization" };
+//!     org = Class { annotations: [label] };
+//! }
+//! .unwrap();
+//!
+//! let org_cid = &graph["org"].cid;
+//! ```
+//!
+//! Field values can be names of other entities declared in the same block, string literals
+//! (used as raw bytes), or any expression implementing [`CidReference`] (entities, CIDs or CID
+//! bytes). Expressions that consist of more than one token have to be wrapped in parentheses.
+//! Fields that are not declared are left empty, and every declared entity is validated, so that
+//! leaving out a required field is an error.
+use super::builder::CidReference;
+use super::{Entity, Validate, Violation};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Value of a single element of a field, before references to other declarations are resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphValue {
+    /// Reference to another entity declared in the same graph.
+    Local(&'static str),
+    Bytes(Vec<u8>),
+}
+
+pub trait ToGraphValue {
+    fn to_graph_value(&self) -> GraphValue;
+}
+
+impl ToGraphValue for GraphValue {
+    fn to_graph_value(&self) -> GraphValue {
+        self.clone()
+    }
+}
+
+impl ToGraphValue for &str {
+    fn to_graph_value(&self) -> GraphValue {
+        GraphValue::Bytes(self.as_bytes().to_vec())
+    }
+}
+
+impl ToGraphValue for String {
+    fn to_graph_value(&self) -> GraphValue {
+        GraphValue::Bytes(self.as_bytes().to_vec())
+    }
+}
+
+impl<T: CidReference + ?Sized> ToGraphValue for T {
+    fn to_graph_value(&self) -> GraphValue {
+        GraphValue::Bytes(self.cid_bytes())
+    }
+}
+
+/// Value of a field, as written in the declaration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphInput {
+    Single(GraphValue),
+    Many(Vec<GraphValue>),
+}
+
+/// Value of a field, after references to other declarations have been resolved to CIDs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolvedInput {
+    Single(Vec<u8>),
+    Many(Vec<Vec<u8>>),
+}
+
+/// Conversion of a resolved value into the type of an entity field.
+pub trait FromResolvedInput: Sized {
+    fn from_resolved_input(field: &'static str, input: ResolvedInput) -> Result<Self, GraphError>;
+}
+
+impl FromResolvedInput for Vec<u8> {
+    fn from_resolved_input(field: &'static str, input: ResolvedInput) -> Result<Self, GraphError> {
+        match input {
+            ResolvedInput::Single(value) => Ok(value),
+            ResolvedInput::Many(_) => Err(GraphError::WrongShape { field }),
+        }
+    }
+}
+
+impl FromResolvedInput for Option<Vec<u8>> {
+    fn from_resolved_input(field: &'static str, input: ResolvedInput) -> Result<Self, GraphError> {
+        match input {
+            ResolvedInput::Single(value) => Ok(Some(value)),
+            ResolvedInput::Many(_) => Err(GraphError::WrongShape { field }),
+        }
+    }
+}
+
+impl FromResolvedInput for Vec<Vec<u8>> {
+    fn from_resolved_input(_: &'static str, input: ResolvedInput) -> Result<Self, GraphError> {
+        match input {
+            ResolvedInput::Single(value) => Ok(vec![value]),
+            ResolvedInput::Many(values) => Ok(values),
+        }
+    }
+}
+
+/// A single `name = Kind { ... }` declaration, as produced by [`rlay_graph!`].
+pub struct GraphDeclaration {
+    pub name: &'static str,
+    /// Values of the declared fields, in declaration order.
+    pub inputs: Vec<GraphInput>,
+    /// Constructs the entity from the resolved values of the declared fields.
+    pub build: fn(Vec<ResolvedInput>) -> Result<Entity, GraphError>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphEntity {
+    pub cid: Vec<u8>,
+    pub entity: Entity,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphError {
+    /// The same name was declared more than once.
+    DuplicateName(&'static str),
+    /// The declarations reference each other in a cycle.
+    Cycle(Vec<&'static str>),
+    /// An array was provided for a single-valued field.
+    WrongShape { field: &'static str },
+    /// The declared entity is not valid, e.g. because a required field was not declared.
+    Invalid {
+        name: &'static str,
+        violations: Vec<Violation>,
+    },
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GraphError::DuplicateName(name) => write!(f, "duplicate declaration of \"{}\"", name),
+            GraphError::Cycle(names) => write!(f, "cyclic references between {:?}", names),
+            GraphError::WrongShape { field } => {
+                write!(f, "array provided for single-valued field \"{}\"", field)
+            }
+            GraphError::Invalid { name, violations } => {
+                let fields: Vec<&str> = violations.iter().map(|v| v.field).collect();
+                write!(
+                    f,
+                    "invalid declaration \"{}\" (fields: {})",
+                    name,
+                    fields.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+fn local_references(inputs: &[GraphInput]) -> Vec<&'static str> {
+    let mut references = Vec::new();
+    for input in inputs {
+        let values = match input {
+            GraphInput::Single(value) => std::slice::from_ref(value),
+            GraphInput::Many(values) => values.as_slice(),
+        };
+        for value in values {
+            if let GraphValue::Local(name) = value {
+                references.push(*name);
+            }
+        }
+    }
+    references
+}
+
+fn resolve_value(value: &GraphValue, graph: &BTreeMap<&'static str, GraphEntity>) -> Vec<u8> {
+    match value {
+        GraphValue::Local(name) => graph[name].cid.clone(),
+        GraphValue::Bytes(bytes) => bytes.clone(),
+    }
+}
+
+/// Builds the entities of the declarations in dependency order, so that the CIDs of referenced
+/// declarations are known when an entity is built.
+pub fn build_graph(
+    declarations: Vec<GraphDeclaration>,
+) -> Result<BTreeMap<&'static str, GraphEntity>, GraphError> {
+    let mut declared: BTreeMap<&'static str, usize> = BTreeMap::new();
+    for (i, declaration) in declarations.iter().enumerate() {
+        if declared.insert(declaration.name, i).is_some() {
+            return Err(GraphError::DuplicateName(declaration.name));
+        }
+    }
+
+    let dependencies: Vec<Vec<&'static str>> = declarations
+        .iter()
+        .map(|declaration| local_references(&declaration.inputs))
+        .collect();
+
+    let mut graph: BTreeMap<&'static str, GraphEntity> = BTreeMap::new();
+    while graph.len() < declarations.len() {
+        // first declaration (in declaration order) whose dependencies are all built
+        let next = declarations
+            .iter()
+            .zip(dependencies.iter())
+            .find(|(decl, deps)| {
+                !graph.contains_key(decl.name) && deps.iter().all(|dep| graph.contains_key(dep))
+            });
+        let declaration = match next {
+            Some((declaration, _)) => declaration,
+            None => {
+                let remaining = declarations
+                    .iter()
+                    .map(|declaration| declaration.name)
+                    .filter(|name| !graph.contains_key(name))
+                    .collect();
+                return Err(GraphError::Cycle(remaining));
+            }
+        };
+
+        let resolved = declaration
+            .inputs
+            .iter()
+            .map(|input| match input {
+                GraphInput::Single(value) => ResolvedInput::Single(resolve_value(value, &graph)),
+                GraphInput::Many(values) => ResolvedInput::Many(
+                    values
+                        .iter()
+                        .map(|value| resolve_value(value, &graph))
+                        .collect(),
+                ),
+            })
+            .collect();
+        let entity = (declaration.build)(resolved)?;
+        entity
+            .validate()
+            .map_err(|violations| GraphError::Invalid {
+                name: declaration.name,
+                violations,
+            })?;
+        let cid = entity.to_bytes();
+        graph.insert(declaration.name, GraphEntity { cid, entity });
+    }
+
+    Ok(graph)
+}
+
+/// Declares a graph of named entities that reference each other by name.
+///
+/// Evaluates to a `Result<BTreeMap<&'static str, GraphEntity>, GraphError>`. See the
+/// [module documentation](ontology/graph/index.html) for details.
+#[macro_export]
+macro_rules! rlay_graph {
+    (@input [$($elem:expr),* $(,)*]) => {
+        $crate::ontology::graph::GraphInput::Many(vec![
+            $($crate::ontology::graph::ToGraphValue::to_graph_value(&$elem)),*
+        ])
+    };
+    (@input $value:expr) => {
+        $crate::ontology::graph::GraphInput::Single(
+            $crate::ontology::graph::ToGraphValue::to_graph_value(&$value)
+        )
+    };
+    ($($name:ident = $kind:ident { $($field:ident : $value:tt),* $(,)* };)*) => {{
+        $(
+            #[allow(unused_variables)]
+            let $name = $crate::ontology::graph::GraphValue::Local(stringify!($name));
+        )*
+        let declarations = vec![
+            $(
+                $crate::ontology::graph::GraphDeclaration {
+                    name: stringify!($name),
+                    inputs: vec![$($crate::rlay_graph!(@input $value)),*],
+                    build: |inputs| {
+                        #[allow(unused_mut, unused_variables)]
+                        let mut inputs = inputs.into_iter();
+                        #[allow(clippy::needless_update)]
+                        let entity = $crate::ontology::$kind {
+                            $(
+                                $field: $crate::ontology::graph::FromResolvedInput::from_resolved_input(
+                                    stringify!($field),
+                                    inputs.next().unwrap(),
+                                )?,
+                            )*
+                            ..Default::default()
+                        };
+                        Ok(entity.into())
+                    },
+                },
+            )*
+        ];
+        $crate::ontology::graph::build_graph(declarations)
+    }};
+}
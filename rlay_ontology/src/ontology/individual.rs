@@ -0,0 +1,255 @@
+//! Composition of `Individual` entities from loose assertions, and the reverse.
+use super::*;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndividualError {
+    /// The entity is not an assertion kind that an `Individual` can hold.
+    UnsupportedKind(EntityKind),
+    /// The assertions are about different subjects.
+    SubjectMismatch { expected: Vec<u8>, found: Vec<u8> },
+    /// A referenced assertion could not be resolved.
+    Unresolved(Vec<u8>),
+    /// A referenced assertion resolved to an entity of another kind than the field holds.
+    WrongKind {
+        cid: Vec<u8>,
+        expected: EntityKind,
+        found: EntityKind,
+    },
+}
+
+impl fmt::Display for IndividualError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IndividualError::UnsupportedKind(kind) => {
+                write!(f, "{:?} can not be part of an Individual", kind)
+            }
+            IndividualError::SubjectMismatch { .. } => {
+                write!(f, "assertions are about different subjects")
+            }
+            IndividualError::Unresolved(_) => write!(f, "assertion could not be resolved"),
+            IndividualError::WrongKind {
+                expected, found, ..
+            } => write!(f, "expected {:?}, found {:?}", expected, found),
+        }
+    }
+}
+
+impl std::error::Error for IndividualError {}
+
+/// The resolved assertions of an `Individual`, by kind.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndividualAssertions {
+    pub class_assertions: Vec<ClassAssertion>,
+    pub negative_class_assertions: Vec<NegativeClassAssertion>,
+    pub object_property_assertions: Vec<ObjectPropertyAssertion>,
+    pub negative_object_property_assertions: Vec<NegativeObjectPropertyAssertion>,
+    pub data_property_assertions: Vec<DataPropertyAssertion>,
+    pub negative_data_property_assertions: Vec<NegativeDataPropertyAssertion>,
+}
+
+impl IndividualAssertions {
+    /// All assertions as entities, in field order.
+    pub fn into_entities(self) -> Vec<Entity> {
+        let mut entities: Vec<Entity> = Vec::new();
+        entities.extend(self.class_assertions.into_iter().map(Into::into));
+        entities.extend(self.negative_class_assertions.into_iter().map(Into::into));
+        entities.extend(self.object_property_assertions.into_iter().map(Into::into));
+        entities.extend(
+            self.negative_object_property_assertions
+                .into_iter()
+                .map(Into::into),
+        );
+        entities.extend(self.data_property_assertions.into_iter().map(Into::into));
+        entities.extend(
+            self.negative_data_property_assertions
+                .into_iter()
+                .map(Into::into),
+        );
+        entities
+    }
+
+    /// Adds the assertion to the field of its kind, ignoring entities of other kinds.
+    fn push(&mut self, entity: Entity) {
+        match entity {
+            Entity::ClassAssertion(ent) => self.class_assertions.push(ent),
+            Entity::NegativeClassAssertion(ent) => self.negative_class_assertions.push(ent),
+            Entity::ObjectPropertyAssertion(ent) => self.object_property_assertions.push(ent),
+            Entity::NegativeObjectPropertyAssertion(ent) => {
+                self.negative_object_property_assertions.push(ent)
+            }
+            Entity::DataPropertyAssertion(ent) => self.data_property_assertions.push(ent),
+            Entity::NegativeDataPropertyAssertion(ent) => {
+                self.negative_data_property_assertions.push(ent)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Resolves the assertions referenced by a field, checking that they are of the expected kind.
+fn resolve_field<R: EntityResolver + ?Sized>(
+    cids: &[Vec<u8>],
+    resolver: &R,
+    expected: &EntityKind,
+) -> Result<Vec<Entity>, IndividualError> {
+    cids.iter()
+        .map(|cid| {
+            let entity = resolver
+                .resolve(cid)
+                .ok_or_else(|| IndividualError::Unresolved(cid.clone()))?;
+            let found = entity.kind();
+            match found == *expected {
+                true => Ok(entity),
+                false => Err(IndividualError::WrongKind {
+                    cid: cid.clone(),
+                    expected: expected.clone(),
+                    found,
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Checks that all assertions that have a subject have the same one.
+fn check_subject<'a, I: IntoIterator<Item = &'a Entity>>(
+    assertions: I,
+) -> Result<(), IndividualError> {
+    let mut subject: Option<&Vec<u8>> = None;
+    for found in assertions.into_iter().filter_map(Entity::get_subject) {
+        match subject {
+            Some(expected) if expected != found => {
+                return Err(IndividualError::SubjectMismatch {
+                    expected: expected.clone(),
+                    found: found.clone(),
+                })
+            }
+            _ => subject = Some(found),
+        }
+    }
+    Ok(())
+}
+
+fn union(left: &[Vec<u8>], right: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    let mut merged: Vec<Vec<u8>> = left.iter().chain(right.iter()).cloned().collect();
+    merged.sort();
+    merged.dedup();
+    merged
+}
+
+impl Individual {
+    /// The assertion fields, with the kind of the assertions each of them holds.
+    fn assertion_fields(&self) -> [(&Vec<Vec<u8>>, EntityKind); 6] {
+        [
+            (&self.class_assertions, EntityKind::ClassAssertion),
+            (
+                &self.negative_class_assertions,
+                EntityKind::NegativeClassAssertion,
+            ),
+            (
+                &self.object_property_assertions,
+                EntityKind::ObjectPropertyAssertion,
+            ),
+            (
+                &self.negative_object_property_assertions,
+                EntityKind::NegativeObjectPropertyAssertion,
+            ),
+            (
+                &self.data_property_assertions,
+                EntityKind::DataPropertyAssertion,
+            ),
+            (
+                &self.negative_data_property_assertions,
+                EntityKind::NegativeDataPropertyAssertion,
+            ),
+        ]
+    }
+
+    /// Builds an `Individual` referencing the assertions, sorted into the fields by kind.
+    ///
+    /// All assertions that have a subject need to have the same one.
+    pub fn from_assertions<'a, I: IntoIterator<Item = &'a Entity>>(
+        assertions: I,
+    ) -> Result<Self, IndividualError> {
+        let assertions: Vec<&Entity> = assertions.into_iter().collect();
+        check_subject(assertions.iter().cloned())?;
+
+        let mut individual = Individual::default();
+        for entity in assertions {
+            let field = match entity {
+                Entity::ClassAssertion(_) => &mut individual.class_assertions,
+                Entity::NegativeClassAssertion(_) => &mut individual.negative_class_assertions,
+                Entity::ObjectPropertyAssertion(_) => &mut individual.object_property_assertions,
+                Entity::NegativeObjectPropertyAssertion(_) => {
+                    &mut individual.negative_object_property_assertions
+                }
+                Entity::DataPropertyAssertion(_) => &mut individual.data_property_assertions,
+                Entity::NegativeDataPropertyAssertion(_) => {
+                    &mut individual.negative_data_property_assertions
+                }
+                _ => return Err(IndividualError::UnsupportedKind(entity.kind())),
+            };
+
+            let cid = entity.to_bytes();
+            if !field.contains(&cid) {
+                field.push(cid);
+            }
+        }
+
+        individual.canonicalize();
+        Ok(individual)
+    }
+
+    /// Resolves all referenced assertions.
+    pub fn assertions<R: EntityResolver + ?Sized>(
+        &self,
+        resolver: &R,
+    ) -> Result<IndividualAssertions, IndividualError> {
+        let mut assertions = IndividualAssertions::default();
+        for (cids, expected) in self.assertion_fields().iter() {
+            for entity in resolve_field(cids, resolver, expected)? {
+                assertions.push(entity);
+            }
+        }
+        Ok(assertions)
+    }
+
+    /// Combines the annotations and assertions of two `Individual`s about the same subject.
+    ///
+    /// Fails if an assertion of either `Individual` can't be resolved, or if the assertions are
+    /// about different subjects.
+    pub fn merge<R: EntityResolver + ?Sized>(
+        &self,
+        other: &Individual,
+        resolver: &R,
+    ) -> Result<Individual, IndividualError> {
+        let mut assertions = self.assertions(resolver)?.into_entities();
+        assertions.extend(other.assertions(resolver)?.into_entities());
+        check_subject(&assertions)?;
+
+        Ok(Individual {
+            annotations: union(&self.annotations, &other.annotations),
+            class_assertions: union(&self.class_assertions, &other.class_assertions),
+            negative_class_assertions: union(
+                &self.negative_class_assertions,
+                &other.negative_class_assertions,
+            ),
+            object_property_assertions: union(
+                &self.object_property_assertions,
+                &other.object_property_assertions,
+            ),
+            negative_object_property_assertions: union(
+                &self.negative_object_property_assertions,
+                &other.negative_object_property_assertions,
+            ),
+            data_property_assertions: union(
+                &self.data_property_assertions,
+                &other.data_property_assertions,
+            ),
+            negative_data_property_assertions: union(
+                &self.negative_data_property_assertions,
+                &other.negative_data_property_assertions,
+            ),
+        })
+    }
+}
@@ -1,6 +1,14 @@
+#[cfg(feature = "std")]
+pub mod canonical;
+#[cfg(feature = "std")]
+pub mod graph;
+#[cfg(feature = "std")]
+pub mod individual;
+pub mod schema;
 #[cfg(feature = "web3_compat")]
 pub mod web3;
 
+pub use self::schema::{FieldSchema, KindSchema};
 #[cfg(feature = "web3_compat")]
 use self::web3::{FromABIV2Response, FromABIV2ResponseHinted};
 #[cfg(feature = "std")]
@@ -56,6 +64,68 @@ pub trait DataFieldNames {
     fn data_field_names() -> &'static [&'static str];
 }
 
+/// Looks up entities by their CID.
+pub trait EntityResolver {
+    fn resolve(&self, cid: &[u8]) -> Option<Entity>;
+}
+
+#[cfg(feature = "std")]
+impl EntityResolver for std::collections::BTreeMap<Vec<u8>, Entity> {
+    fn resolve(&self, cid: &[u8]) -> Option<Entity> {
+        self.get(cid).cloned()
+    }
+}
+
+#[cfg(feature = "std")]
+impl EntityResolver for std::collections::HashMap<Vec<u8>, Entity> {
+    fn resolve(&self, cid: &[u8]) -> Option<Entity> {
+        self.get(cid).cloned()
+    }
+}
+
+/// Checks the semantic well-formedness of an entity, beyond what the type system enforces.
+#[delegatable_trait]
+pub trait Validate {
+    /// Returns every violation that was found.
+    fn validate(&self) -> Result<(), Vec<Violation>>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// Name of the offending field, as found in the field names of the kind.
+    pub field: &'static str,
+    pub kind: ViolationKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViolationKind {
+    /// A required or present field holds empty bytes.
+    Empty,
+    /// An element of an array field holds empty bytes.
+    EmptyElement { index: usize },
+    /// An array field holds fewer elements than the grammar requires.
+    TooFewElements { min: usize, actual: usize },
+}
+
+/// Value of a single entity field, as used for accessing fields by name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    /// Value of a required field.
+    Bytes(Vec<u8>),
+    /// Value of an optional field.
+    OptionalBytes(Option<Vec<u8>>),
+    /// Value of an array field.
+    Array(Vec<Vec<u8>>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldError {
+    /// The entity kind has no field with the provided name.
+    UnknownField(String),
+    /// The value does not have the shape of the field (e.g. `Array` for a required field).
+    WrongShape { field: &'static str },
+}
+
 // include!(concat!(env!("OUT_DIR"), "/rlay.ontology.rs"));
 include!(concat!(env!("OUT_DIR"), "/rlay.ontology.entities.rs"));
 
@@ -81,11 +151,41 @@ impl Entity {
     }
 
     pub fn get_subject(&self) -> Option<&Vec<u8>> {
-        match &self {
-            Entity::ClassAssertion(ent) => ent.get_subject(),
-            Entity::NegativeClassAssertion(ent) => ent.get_subject(),
-            _ => None,
+        self.as_assertion().and_then(|assertion| assertion.subject())
+    }
+
+    /// Replaces every CID in the CID fields that is a key of `mapping` with its value, and
+    /// re-canonicalizes the entity. Returns whether a reference was replaced.
+    #[cfg(feature = "std")]
+    pub fn replace_references(
+        &mut self,
+        mapping: &std::collections::BTreeMap<Vec<u8>, Vec<u8>>,
+    ) -> bool {
+        let mut replaced = false;
+        let mut replace = |value: Vec<u8>| match mapping.get(&value) {
+            Some(new) => {
+                replaced = true;
+                new.clone()
+            }
+            None => value,
+        };
+        for name in self.cid_field_names() {
+            let value = match self.get_field(name) {
+                Some(FieldValue::Bytes(value)) => FieldValue::Bytes(replace(value)),
+                Some(FieldValue::OptionalBytes(value)) => {
+                    FieldValue::OptionalBytes(value.map(&mut replace))
+                }
+                Some(FieldValue::Array(values)) => {
+                    FieldValue::Array(values.into_iter().map(&mut replace).collect())
+                }
+                None => continue,
+            };
+            self.set_field(name, value).unwrap();
+        }
+        if replaced {
+            self.canonicalize();
         }
+        replaced
     }
 
     pub fn as_class_assertion(&self) -> Option<&ClassAssertion> {
@@ -125,34 +225,29 @@ pub mod compact {
 mod custom {
     use super::*;
 
+    /// Implemented for every pair of assertion kinds `X` and `NegativeX`.
     pub trait GetAssertionComplement {
         type Complement;
 
         fn get_assertion_complement(&self) -> Self::Complement;
     }
 
-    impl GetAssertionComplement for ClassAssertion {
-        type Complement = NegativeClassAssertion;
+    /// Uniform view of the assertion kinds as (subject, predicate, object) triples.
+    pub trait Assertion {
+        fn subject(&self) -> Option<&Vec<u8>>;
 
-        fn get_assertion_complement(&self) -> Self::Complement {
-            NegativeClassAssertion {
-                annotations: vec![],
-                subject: self.subject.clone(),
-                class: self.class.clone(),
-            }
-        }
-    }
+        /// The property, or the class for class assertions.
+        fn predicate(&self) -> Option<&Vec<u8>>;
 
-    impl GetAssertionComplement for NegativeClassAssertion {
-        type Complement = ClassAssertion;
+        /// The target or value, or `None` for class assertions.
+        fn object(&self) -> Option<&Vec<u8>>;
+    }
 
-        fn get_assertion_complement(&self) -> Self::Complement {
-            ClassAssertion {
-                annotations: vec![],
-                subject: self.subject.clone(),
-                class: self.class.clone(),
-            }
-        }
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Polarity {
+        Positive,
+        Negative,
+        NotAnAssertion,
     }
 
     impl ClassAssertion {
@@ -168,11 +263,70 @@ mod custom {
     }
 }
 
+/// Fluent builders for all entity kinds, that compute the CIDs of referenced entities.
+#[cfg(feature = "std")]
+pub mod builder {
+    use super::*;
+    use std::fmt;
+
+    /// A value that can be used for a reference field: either an entity, whose CID is computed,
+    /// or a CID.
+    pub trait CidReference {
+        fn cid_bytes(&self) -> Vec<u8>;
+    }
+
+    impl CidReference for Entity {
+        fn cid_bytes(&self) -> Vec<u8> {
+            self.to_bytes()
+        }
+    }
+
+    impl CidReference for Cid {
+        fn cid_bytes(&self) -> Vec<u8> {
+            self.to_bytes()
+        }
+    }
+
+    /// Bytes of an already computed CID.
+    impl CidReference for [u8] {
+        fn cid_bytes(&self) -> Vec<u8> {
+            self.to_vec()
+        }
+    }
+
+    /// Bytes of an already computed CID.
+    impl CidReference for Vec<u8> {
+        fn cid_bytes(&self) -> Vec<u8> {
+            self.clone()
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum BuildError {
+        /// A required field has not been set.
+        MissingField(&'static str),
+    }
+
+    impl fmt::Display for BuildError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                BuildError::MissingField(field) => {
+                    write!(f, "missing required field \"{}\"", field)
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for BuildError {}
+
+    include!(concat!(env!("OUT_DIR"), "/rlay.ontology.builder.rs"));
+}
+
 /// Serialization format for the canonical v0 cbor-based format.
 #[cfg(feature = "std")]
 pub mod v0 {
     use super::*;
-    use crate::ontology::compact::FormatCompact;
+    use crate::ontology::compact::*;
     use integer_encoding::VarIntReader;
     use integer_encoding::VarIntWriter;
 
@@ -0,0 +1,40 @@
+//! Runtime schema of the entity kinds, generated from the same source as the entity structs.
+
+/// Schema of a single entity kind.
+#[derive(Debug, PartialEq)]
+pub struct KindSchema {
+    pub name: &'static str,
+    /// Fields in declaration order.
+    pub fields: &'static [FieldSchema],
+    /// Expression group the kind belongs to, e.g. `ClassExpression`.
+    pub expression_kind: Option<&'static str>,
+    pub kind_id: u64,
+    pub cid_prefix: u64,
+}
+
+impl KindSchema {
+    pub fn field(&self, name: &str) -> Option<&'static FieldSchema> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// Schema of a single field of an entity kind.
+#[derive(Debug, PartialEq)]
+pub struct FieldSchema {
+    pub name: &'static str,
+    /// Declared kind of the field, e.g. `ClassExpression[]` or `IRI`.
+    pub kind: &'static str,
+    pub required: bool,
+    pub array: bool,
+    /// Whether the field holds data instead of a CID (see `DataFieldNames`).
+    pub data: bool,
+    pub min_cardinality: Option<usize>,
+}
+
+impl FieldSchema {
+    /// Declared kind of a single value of the field, e.g. `ClassExpression` for an array field of
+    /// kind `ClassExpression[]`.
+    pub fn element_kind(&self) -> &'static str {
+        self.kind.trim_end_matches("[]")
+    }
+}
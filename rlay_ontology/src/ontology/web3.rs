@@ -127,14 +127,20 @@ impl<'de> DeserializeFormatWeb3<'de> for Vec<u8> {
     }
 }
 
-/// Decode a single ethabi param of type bytes
+/// Decode a single ethabi param of type bytes. A missing param decodes as empty.
 fn decode_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.is_empty() {
+        return Vec::new();
+    }
     let length = U256::from_big_endian(&bytes[0..32]);
     bytes[((32) as usize)..((length).as_u64() as usize + 32)].to_owned()
 }
 
-/// Decode a single ethabi param of type bytes[]
+/// Decode a single ethabi param of type bytes[]. A missing param decodes as empty.
 fn decode_bytes_array(bytes: &[u8]) -> Vec<Vec<u8>> {
+    if bytes.is_empty() {
+        return Vec::new();
+    }
     let num_elements = U256::from_big_endian(&bytes[0..32]);
 
     let element_offsets: Vec<U256> = (0..num_elements.as_u64())
@@ -175,6 +181,14 @@ macro_rules! decode_offset {
     ($bytes_var:ident, $offset_var:ident, $start:expr, $end:expr) => {
         let $offset_var = U256::from_big_endian(&$bytes_var[$start..$end]);
     };
+    // The head ends where the data of the first param starts. Params missing from the head, as
+    // in payloads encoded before fields were added to the kind, start at the end of the data.
+    ($bytes_var:ident, $offset_var:ident, $start:expr, $end:expr, $head_end:expr) => {
+        let $offset_var = match $end as u64 > $head_end.as_u64() {
+            true => U256::from($bytes_var.len() as u64),
+            false => U256::from_big_endian(&$bytes_var[$start..$end]),
+        };
+    };
 }
 
 macro_rules! decode_param {
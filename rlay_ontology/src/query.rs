@@ -0,0 +1,864 @@
+//! A small subset of SPARQL, evaluated over the positive assertions of an [`AssertionIndex`].
+//!
+//! ```text
+//! SELECT ?org ?name WHERE {
+//!     ?org a <zCLASS> .
+//!     ?org <zPROPERTY> ?name .
+//!     FILTER(?name != "Acme")
+//! } LIMIT 10
+//! ```
+//!
+//! Supported are basic graph patterns, `FILTER`s with a single comparison and `LIMIT`. CIDs are
+//! written as multibase-encoded strings in angle brackets, data values as string or number
+//! literals. `a` matches class assertions, every other predicate matches property and annotation
+//! assertions.
+//!
+//! The targets of data property assertions are decoded as typed [`LiteralValue`]s, and the values
+//! of annotation assertions, which are raw text, as strings. Values are compared by their
+//! datatypes: numbers numerically, strings, booleans and bytes by their contents, and date times
+//! only if they are in the same timezone. Comparisons between values of different types, like a
+//! string and a number, never match.
+//!
+//! Labels are only found through annotation assertions, like `?company <zLABEL> "Acme"`.
+//! Annotations in the `annotations` field of an entity are not indexed, and can't be queried.
+//!
+//! [`LiteralValue`]: ../literal/enum.LiteralValue.html
+use crate::index::{AssertionIndex, AssertionPattern, IndexedAssertion};
+use crate::literal::LiteralValue;
+use crate::ontology::{EntityKind, Polarity};
+use multibase::Base;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A value bound to a variable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Cid(Vec<u8>),
+    /// Data that is neither a typed value nor text, like a malformed data property target.
+    Data(Vec<u8>),
+    /// A typed value, or the text of an annotation assertion as a string.
+    Literal(LiteralValue),
+}
+
+impl Value {
+    /// The bytes of a CID or of untyped data.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Cid(bytes) => Some(bytes),
+            Value::Data(bytes) => Some(bytes),
+            Value::Literal(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Cid(bytes) => write!(f, "<{}>", multibase::encode(Base::Base58btc, bytes)),
+            Value::Data(bytes) => write!(f, "{}", LiteralValue::Bytes(bytes.clone())),
+            Value::Literal(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryError {
+    /// The query ended where more input was expected.
+    UnexpectedEnd,
+    UnexpectedToken {
+        offset: usize,
+        found: String,
+    },
+    /// A string in angle brackets was not a multibase-encoded CID.
+    InvalidCid {
+        offset: usize,
+        cid: String,
+    },
+    /// A variable was selected or filtered on, without appearing in a triple pattern.
+    UnknownVariable(String),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QueryError::UnexpectedEnd => write!(f, "unexpected end of query"),
+            QueryError::UnexpectedToken { offset, found } => {
+                write!(f, "unexpected \"{}\" at offset {}", found, offset)
+            }
+            QueryError::InvalidCid { offset, cid } => {
+                write!(f, "invalid CID \"{}\" at offset {}", cid, offset)
+            }
+            QueryError::UnknownVariable(name) => write!(f, "unknown variable ?{}", name),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Variable(String),
+    Iri(String),
+    Literal(String),
+    Number(String),
+    Word(String),
+    Symbol(&'static str),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Variable(name) => write!(f, "?{}", name),
+            Token::Iri(iri) => write!(f, "<{}>", iri),
+            Token::Literal(literal) => write!(f, "{:?}", literal),
+            Token::Number(number) => write!(f, "{}", number),
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Symbol(symbol) => write!(f, "{}", symbol),
+        }
+    }
+}
+
+fn tokenize(query: &str) -> Result<Vec<(usize, Token)>, QueryError> {
+    let chars: Vec<(usize, char)> = query.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    let take_while = |start: usize, pred: &dyn Fn(char) -> bool| -> usize {
+        let mut end = start;
+        while end < chars.len() && pred(chars[end].1) {
+            end += 1;
+        }
+        end
+    };
+    let slice =
+        |start: usize, end: usize| -> String { chars[start..end].iter().map(|(_, c)| c).collect() };
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '_';
+    // character at the index, or `\0` past the end of the query
+    let char_at = |index: usize| chars.get(index).map_or('\0', |(_, c)| *c);
+
+    while i < chars.len() {
+        let (offset, c) = chars[i];
+        let next = char_at(i + 1);
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let (token, end) = match c {
+            '?' => {
+                let end = take_while(i + 1, &is_name_char);
+                if end == i + 1 {
+                    return Err(QueryError::UnexpectedToken {
+                        offset,
+                        found: "?".to_owned(),
+                    });
+                }
+                (Token::Variable(slice(i + 1, end)), end)
+            }
+            '"' => {
+                let mut literal = String::new();
+                let mut end = i + 1;
+                loop {
+                    match chars.get(end).map(|(_, c)| *c) {
+                        None => return Err(QueryError::UnexpectedEnd),
+                        Some('"') => break,
+                        Some('\\') => {
+                            let escaped = chars.get(end + 1).ok_or(QueryError::UnexpectedEnd)?;
+                            literal.push(escaped.1);
+                            end += 2;
+                        }
+                        Some(c) => {
+                            literal.push(c);
+                            end += 1;
+                        }
+                    }
+                }
+                (Token::Literal(literal), end + 1)
+            }
+            '<' | '>' | '!' | '=' if next == '=' => {
+                let symbol = match c {
+                    '<' => "<=",
+                    '>' => ">=",
+                    '!' => "!=",
+                    _ => "==",
+                };
+                (Token::Symbol(symbol), i + 2)
+            }
+            // `<` directly followed by a CID, as opposed to the comparison operator
+            '<' if next.is_alphanumeric() && !next.is_ascii_digit() => {
+                let end = take_while(i + 1, &|c| c != '>' && !c.is_whitespace());
+                if char_at(end) != '>' {
+                    return Err(QueryError::UnexpectedEnd);
+                }
+                (Token::Iri(slice(i + 1, end)), end + 1)
+            }
+            '{' | '}' | '.' | '(' | ')' | '*' | '<' | '>' | '=' => {
+                let symbol = match c {
+                    '{' => "{",
+                    '}' => "}",
+                    '.' => ".",
+                    '(' => "(",
+                    ')' => ")",
+                    '*' => "*",
+                    '<' => "<",
+                    '>' => ">",
+                    _ => "=",
+                };
+                (Token::Symbol(symbol), i + 1)
+            }
+            c if c.is_ascii_digit() || (c == '-' && next.is_ascii_digit()) => {
+                let mut end = take_while(i + 1, &|c| c.is_ascii_digit());
+                // a fractional part, as opposed to a `.` that terminates the triple pattern
+                if char_at(end) == '.' && char_at(end + 1).is_ascii_digit() {
+                    end = take_while(end + 1, &|c| c.is_ascii_digit());
+                }
+                (Token::Number(slice(i, end)), end)
+            }
+            c if is_name_char(c) => {
+                let end = take_while(i, &is_name_char);
+                (Token::Word(slice(i, end)), end)
+            }
+            c => {
+                return Err(QueryError::UnexpectedToken {
+                    offset,
+                    found: c.to_string(),
+                })
+            }
+        };
+        tokens.push((offset, token));
+        i = end;
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Variable(String),
+    Value(Value),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Predicate {
+    /// `a`, matching class assertions.
+    Type,
+    Term(Term),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct TriplePattern {
+    subject: Term,
+    predicate: Predicate,
+    object: Term,
+}
+
+impl TriplePattern {
+    fn variables(&self) -> Vec<&str> {
+        let mut terms = vec![&self.subject, &self.object];
+        if let Predicate::Term(term) = &self.predicate {
+            terms.push(term);
+        }
+        terms
+            .into_iter()
+            .filter_map(|term| match term {
+                Term::Variable(name) => Some(name.as_str()),
+                Term::Value(_) => None,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Filter {
+    left: Term,
+    operator: Operator,
+    right: Term,
+}
+
+/// A parsed query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    /// Selected variables, in order.
+    variables: Vec<String>,
+    patterns: Vec<TriplePattern>,
+    filters: Vec<Filter>,
+    limit: Option<usize>,
+}
+
+/// The integer or decimal written as the number, in its canonical lexical form.
+fn number_value(number: &str) -> LiteralValue {
+    let (negative, unsigned) = match number.starts_with('-') {
+        true => (true, &number[1..]),
+        false => (false, number),
+    };
+    let (int, frac) = match unsigned.find('.') {
+        Some(pos) => (&unsigned[..pos], Some(&unsigned[pos + 1..])),
+        None => (unsigned, None),
+    };
+    let int = match int.trim_start_matches('0') {
+        "" => "0",
+        int => int,
+    };
+    let (unsigned, is_zero) = match frac.map(|frac| frac.trim_end_matches('0')) {
+        Some("") => (format!("{}.0", int), int == "0"),
+        Some(frac) => (format!("{}.{}", int, frac), false),
+        None => (int.to_owned(), int == "0"),
+    };
+    let lexical = match negative && !is_zero {
+        true => format!("-{}", unsigned),
+        false => unsigned,
+    };
+    match frac {
+        Some(_) => LiteralValue::Decimal(lexical),
+        None => LiteralValue::Integer(lexical),
+    }
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn next(&mut self) -> Result<(usize, Token), QueryError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(QueryError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn unexpected<T>(offset: usize, token: &Token) -> Result<T, QueryError> {
+        Err(QueryError::UnexpectedToken {
+            offset,
+            found: token.to_string(),
+        })
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) => word.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        match self.peek() {
+            Some(Token::Symbol(found)) => *found == symbol,
+            _ => false,
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), QueryError> {
+        let (offset, token) = self.next()?;
+        match &token {
+            Token::Word(word) if word.eq_ignore_ascii_case(keyword) => Ok(()),
+            _ => Self::unexpected(offset, &token),
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), QueryError> {
+        let (offset, token) = self.next()?;
+        match &token {
+            Token::Symbol(found) if *found == symbol => Ok(()),
+            _ => Self::unexpected(offset, &token),
+        }
+    }
+
+    fn term(&mut self) -> Result<Term, QueryError> {
+        let (offset, token) = self.next()?;
+        match token {
+            Token::Variable(name) => Ok(Term::Variable(name)),
+            Token::Iri(iri) => match multibase::decode(&iri) {
+                Ok((_, bytes)) => Ok(Term::Value(Value::Cid(bytes))),
+                Err(_) => Err(QueryError::InvalidCid { offset, cid: iri }),
+            },
+            Token::Literal(literal) => {
+                Ok(Term::Value(Value::Literal(LiteralValue::String(literal))))
+            }
+            Token::Number(number) => Ok(Term::Value(Value::Literal(number_value(&number)))),
+            token => Self::unexpected(offset, &token),
+        }
+    }
+
+    fn filter(&mut self) -> Result<Filter, QueryError> {
+        self.expect_keyword("FILTER")?;
+        self.expect_symbol("(")?;
+        let left = self.term()?;
+        let (offset, token) = self.next()?;
+        let operator = match token {
+            Token::Symbol("=") | Token::Symbol("==") => Operator::Eq,
+            Token::Symbol("!=") => Operator::Ne,
+            Token::Symbol("<") => Operator::Lt,
+            Token::Symbol("<=") => Operator::Le,
+            Token::Symbol(">") => Operator::Gt,
+            Token::Symbol(">=") => Operator::Ge,
+            token => return Self::unexpected(offset, &token),
+        };
+        let right = self.term()?;
+        self.expect_symbol(")")?;
+        Ok(Filter {
+            left,
+            operator,
+            right,
+        })
+    }
+
+    fn triple_pattern(&mut self) -> Result<TriplePattern, QueryError> {
+        let subject = self.term()?;
+        let predicate = match self.peek() {
+            Some(Token::Word(word)) if word == "a" => {
+                self.position += 1;
+                Predicate::Type
+            }
+            _ => Predicate::Term(self.term()?),
+        };
+        let object = self.term()?;
+        Ok(TriplePattern {
+            subject,
+            predicate,
+            object,
+        })
+    }
+
+    fn query(&mut self) -> Result<Query, QueryError> {
+        self.expect_keyword("SELECT")?;
+        let mut selected = None;
+        if self.is_symbol("*") {
+            self.position += 1;
+        } else {
+            let mut variables = Vec::new();
+            while let Some(Token::Variable(name)) = self.peek() {
+                variables.push(name.clone());
+                self.position += 1;
+            }
+            if variables.is_empty() {
+                let (offset, token) = self.next()?;
+                return Self::unexpected(offset, &token);
+            }
+            selected = Some(variables);
+        }
+
+        if self.is_keyword("WHERE") {
+            self.position += 1;
+        }
+        self.expect_symbol("{")?;
+        let mut patterns = Vec::new();
+        let mut filters = Vec::new();
+        while !self.is_symbol("}") {
+            match self.is_keyword("FILTER") {
+                true => filters.push(self.filter()?),
+                false => patterns.push(self.triple_pattern()?),
+            }
+            if self.is_symbol(".") {
+                self.position += 1;
+            }
+        }
+        self.expect_symbol("}")?;
+
+        let mut limit = None;
+        if self.is_keyword("LIMIT") {
+            self.position += 1;
+            let (offset, token) = self.next()?;
+            limit = match &token {
+                Token::Number(number) => match number.parse() {
+                    Ok(limit) => Some(limit),
+                    Err(_) => return Self::unexpected(offset, &token),
+                },
+                _ => return Self::unexpected(offset, &token),
+            };
+        }
+        if let Ok((offset, token)) = self.next() {
+            return Self::unexpected(offset, &token);
+        }
+
+        let mut pattern_variables: Vec<String> = Vec::new();
+        for pattern in patterns.iter() {
+            for name in pattern.variables() {
+                if !pattern_variables.iter().any(|known| known == name) {
+                    pattern_variables.push(name.to_owned());
+                }
+            }
+        }
+        let filter_variables = filters.iter().flat_map(|filter| {
+            vec![&filter.left, &filter.right]
+                .into_iter()
+                .filter_map(|term| match term {
+                    Term::Variable(name) => Some(name.clone()),
+                    Term::Value(_) => None,
+                })
+        });
+        let used_variables: Vec<String> = selected
+            .iter()
+            .flatten()
+            .cloned()
+            .chain(filter_variables)
+            .collect();
+        for name in used_variables {
+            if !pattern_variables.contains(&name) {
+                return Err(QueryError::UnknownVariable(name));
+            }
+        }
+
+        Ok(Query {
+            variables: selected.unwrap_or(pattern_variables),
+            patterns,
+            filters,
+            limit,
+        })
+    }
+}
+
+type Bindings = BTreeMap<String, Value>;
+
+fn resolve<'a>(term: &'a Term, bindings: &'a Bindings) -> Option<&'a Value> {
+    match term {
+        Term::Variable(name) => bindings.get(name),
+        Term::Value(value) => Some(value),
+    }
+}
+
+/// Binds the variable of the term to the value, or checks that the bound value matches.
+fn bind(term: &Term, value: Value, bindings: &mut Bindings) -> bool {
+    match term {
+        Term::Variable(name) => match bindings.get(name) {
+            Some(bound) => compare(bound, &value) == Some(Ordering::Equal),
+            None => {
+                bindings.insert(name.clone(), value);
+                true
+            }
+        },
+        Term::Value(constant) => compare(constant, &value) == Some(Ordering::Equal),
+    }
+}
+
+/// Sign, integer digits and fractional digits without trailing zeros of a canonical integer or
+/// decimal.
+fn split_number(number: &str) -> (bool, &str, &str) {
+    let (negative, unsigned) = match number.starts_with('-') {
+        true => (true, &number[1..]),
+        false => (false, number),
+    };
+    match unsigned.find('.') {
+        Some(pos) => (
+            negative,
+            &unsigned[..pos],
+            unsigned[pos + 1..].trim_end_matches('0'),
+        ),
+        None => (negative, unsigned, ""),
+    }
+}
+
+/// Orders two canonical integers or decimals by their numeric values.
+fn compare_numbers(left: &str, right: &str) -> Ordering {
+    let (left_negative, left_int, left_frac) = split_number(left);
+    let (right_negative, right_int, right_frac) = split_number(right);
+    match (left_negative, right_negative) {
+        (false, true) => Ordering::Greater,
+        (true, false) => Ordering::Less,
+        _ => {
+            // canonical integer digits have no leading zeros
+            let magnitude = left_int
+                .len()
+                .cmp(&right_int.len())
+                .then_with(|| left_int.cmp(right_int))
+                .then_with(|| left_frac.cmp(right_frac));
+            match left_negative {
+                true => magnitude.reverse(),
+                false => magnitude,
+            }
+        }
+    }
+}
+
+/// Year, month to seconds, fractional seconds and timezone of a canonical date time.
+fn split_date_time(date_time: &str) -> (&str, &str, &str, &str) {
+    let year_end = date_time.find('-').unwrap();
+    let seconds_end = year_end + "-00-00T00:00:00".len();
+    let rest = &date_time[seconds_end..];
+    let (fraction, zone) = match rest.starts_with('.') {
+        true => {
+            let end = rest[1..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(rest.len(), |pos| pos + 1);
+            (&rest[1..end], &rest[end..])
+        }
+        false => ("", rest),
+    };
+    (
+        &date_time[..year_end],
+        &date_time[year_end..seconds_end],
+        fraction,
+        zone,
+    )
+}
+
+/// Orders two canonical date times in the same timezone, or returns `None`.
+fn compare_date_times(left: &str, right: &str) -> Option<Ordering> {
+    let (left_year, left_time, left_fraction, left_zone) = split_date_time(left);
+    let (right_year, right_time, right_fraction, right_zone) = split_date_time(right);
+    if left_zone != right_zone {
+        return None;
+    }
+    // canonical years have no leading zeros beyond four digits, and fractions no trailing zeros
+    Some(
+        left_year
+            .len()
+            .cmp(&right_year.len())
+            .then_with(|| left_year.cmp(right_year))
+            .then_with(|| left_time.cmp(right_time))
+            .then_with(|| left_fraction.cmp(right_fraction)),
+    )
+}
+
+/// Orders two typed values of comparable datatypes, or returns `None`.
+fn compare_literals(left: &LiteralValue, right: &LiteralValue) -> Option<Ordering> {
+    match (left, right) {
+        (LiteralValue::String(left), LiteralValue::String(right)) => Some(left.cmp(right)),
+        (
+            LiteralValue::LangString {
+                text: left,
+                lang: left_lang,
+            },
+            LiteralValue::LangString {
+                text: right,
+                lang: right_lang,
+            },
+        ) if left_lang == right_lang => Some(left.cmp(right)),
+        (LiteralValue::Integer(left), LiteralValue::Integer(right))
+        | (LiteralValue::Integer(left), LiteralValue::Decimal(right))
+        | (LiteralValue::Decimal(left), LiteralValue::Integer(right))
+        | (LiteralValue::Decimal(left), LiteralValue::Decimal(right)) => {
+            Some(compare_numbers(left, right))
+        }
+        (LiteralValue::Boolean(left), LiteralValue::Boolean(right)) => Some(left.cmp(right)),
+        (LiteralValue::DateTime(left), LiteralValue::DateTime(right)) => {
+            compare_date_times(left, right)
+        }
+        (LiteralValue::Bytes(left), LiteralValue::Bytes(right)) => Some(left.cmp(right)),
+        _ => None,
+    }
+}
+
+/// Orders two values of the same type, or returns `None`.
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Cid(left), Value::Cid(right)) => Some(left.cmp(right)),
+        (Value::Data(left), Value::Data(right)) => Some(left.cmp(right)),
+        (Value::Literal(left), Value::Literal(right)) => compare_literals(left, right),
+        _ => None,
+    }
+}
+
+impl Filter {
+    fn variables_bound(&self, bindings: &Bindings) -> bool {
+        resolve(&self.left, bindings).is_some() && resolve(&self.right, bindings).is_some()
+    }
+
+    fn matches(&self, bindings: &Bindings) -> bool {
+        let (left, right) = match (
+            resolve(&self.left, bindings),
+            resolve(&self.right, bindings),
+        ) {
+            (Some(left), Some(right)) => (left, right),
+            _ => return false,
+        };
+        let ordering = match compare(left, right) {
+            Some(ordering) => ordering,
+            None => return false,
+        };
+        match self.operator {
+            Operator::Eq => ordering == Ordering::Equal,
+            Operator::Ne => ordering != Ordering::Equal,
+            Operator::Lt => ordering == Ordering::Less,
+            Operator::Le => ordering != Ordering::Greater,
+            Operator::Gt => ordering == Ordering::Greater,
+            Operator::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+impl TriplePattern {
+    fn bound_count(&self, bindings: &Bindings) -> usize {
+        let mut terms = vec![&self.subject, &self.object];
+        if let Predicate::Term(term) = &self.predicate {
+            terms.push(term);
+        }
+        terms
+            .into_iter()
+            .filter(|term| resolve(term, bindings).is_some())
+            .count()
+    }
+
+    /// Extends the bindings with every match of the pattern in the index.
+    fn evaluate(&self, index: &AssertionIndex, bindings: &Bindings) -> Vec<Bindings> {
+        // typed values are matched by `bind` instead, as equal values may be encoded differently
+        let constant = |term: &Term| {
+            resolve(term, bindings)
+                .and_then(Value::as_bytes)
+                .map(|bytes| bytes.to_vec())
+        };
+
+        let mut pattern = AssertionPattern::new().polarity(Polarity::Positive);
+        pattern.subject = constant(&self.subject);
+        match &self.predicate {
+            Predicate::Type => pattern.class = constant(&self.object),
+            Predicate::Term(property) => {
+                pattern.property = constant(property);
+                pattern.object = constant(&self.object);
+            }
+        }
+
+        let mut results = Vec::new();
+        for cid in index.query(&pattern) {
+            let assertion: &IndexedAssertion = index.get(&cid).unwrap();
+            let subject = match &assertion.subject {
+                Some(subject) => Value::Cid(subject.clone()),
+                None => continue,
+            };
+            let mut extended = bindings.clone();
+            let matched = match &self.predicate {
+                Predicate::Type => match &assertion.class {
+                    Some(class) => {
+                        bind(&self.subject, subject, &mut extended)
+                            && bind(&self.object, Value::Cid(class.clone()), &mut extended)
+                    }
+                    None => false,
+                },
+                Predicate::Term(property_term) => match (&assertion.property, &assertion.object) {
+                    (Some(property), Some(object)) => {
+                        let object = object_value(assertion, object);
+                        bind(&self.subject, subject, &mut extended)
+                            && bind(property_term, Value::Cid(property.clone()), &mut extended)
+                            && bind(&self.object, object, &mut extended)
+                    }
+                    _ => false,
+                },
+            };
+            if matched {
+                results.push(extended);
+            }
+        }
+        results
+    }
+}
+
+/// The object of the assertion as a value: the decoded target of a data property assertion, the
+/// text of an annotation assertion, or a CID.
+fn object_value(assertion: &IndexedAssertion, object: &[u8]) -> Value {
+    match assertion.kind {
+        EntityKind::DataPropertyAssertion | EntityKind::NegativeDataPropertyAssertion => {
+            match LiteralValue::from_data(object) {
+                Ok(value) => Value::Literal(value),
+                Err(_) => Value::Data(object.to_vec()),
+            }
+        }
+        _ if assertion.object_is_data => match String::from_utf8(object.to_vec()) {
+            Ok(text) => Value::Literal(LiteralValue::String(text)),
+            Err(_) => Value::Data(object.to_vec()),
+        },
+        _ => Value::Cid(object.to_vec()),
+    }
+}
+
+/// Result of a query, with one column per selected variable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultTable {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl fmt::Display for ResultTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let header: Vec<String> = self
+            .columns
+            .iter()
+            .map(|name| format!("?{}", name))
+            .collect();
+        writeln!(f, "{}", header.join("\t"))?;
+        for row in self.rows.iter() {
+            let cells: Vec<String> = row.iter().map(|value| value.to_string()).collect();
+            writeln!(f, "{}", cells.join("\t"))?;
+        }
+        Ok(())
+    }
+}
+
+impl Query {
+    pub fn parse(query: &str) -> Result<Self, QueryError> {
+        let mut parser = Parser {
+            tokens: tokenize(query)?,
+            position: 0,
+        };
+        parser.query()
+    }
+
+    /// Evaluates the query, joining the triple patterns in order of the number of bound terms.
+    pub fn execute(&self, index: &AssertionIndex) -> ResultTable {
+        let mut solutions: Vec<Bindings> = vec![Bindings::new()];
+        let mut remaining: Vec<&TriplePattern> = self.patterns.iter().collect();
+
+        while !remaining.is_empty() && !solutions.is_empty() {
+            // all solutions bind the same variables, so the first one is representative
+            let next = (0..remaining.len())
+                .max_by_key(|i| {
+                    (
+                        remaining[*i].bound_count(&solutions[0]),
+                        remaining.len() - i,
+                    )
+                })
+                .unwrap();
+            let pattern = remaining.remove(next);
+
+            solutions = solutions
+                .iter()
+                .flat_map(|bindings| pattern.evaluate(index, bindings))
+                .filter(|bindings| {
+                    self.filters
+                        .iter()
+                        .filter(|filter| filter.variables_bound(bindings))
+                        .all(|filter| filter.matches(bindings))
+                })
+                .collect();
+        }
+
+        let mut rows: Vec<Vec<Value>> = solutions
+            .into_iter()
+            .map(|bindings| {
+                self.variables
+                    .iter()
+                    .map(|name| bindings[name].clone())
+                    .collect()
+            })
+            .collect();
+        if let Some(limit) = self.limit {
+            rows.truncate(limit);
+        }
+
+        ResultTable {
+            columns: self.variables.clone(),
+            rows,
+        }
+    }
+}
+
+/// Parses and evaluates a query.
+pub fn query(index: &AssertionIndex, query: &str) -> Result<ResultTable, QueryError> {
+    Ok(Query::parse(query)?.execute(index))
+}
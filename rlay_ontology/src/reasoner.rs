@@ -0,0 +1,566 @@
+//! Classification of OWL 2 EL ontologies by saturation with the EL++ completion rules.
+//!
+//! Axioms are collected from entities (the asserted `superClassExpression`s of `Class`,
+//! `ObjectIntersectionOf` and `ObjectSomeValuesFrom`, and the `superObjectPropertyExpression`s of
+//! `ObjectProperty`) or added directly as [`Concept`] inclusions. The entity model does not carry
+//! the operands of `ObjectIntersectionOf` and `ObjectSomeValuesFrom`, so entities of those kinds
+//! only take part by their CID, unless their structure is provided via [`Reasoner::define`].
+//!
+//! Individuals are realized by treating each of them as a concept that is subsumed by its asserted
+//! classes, and that is linked via `ObjectPropertyAssertion`s to the concepts of other individuals.
+//!
+//! Supported are the completion rules for told subsumption, conjunction, existential
+//! restriction on the right- and left-hand side, the object property hierarchy, and the bottom
+//! concept: [`vocab::OWL_THING`] subsumes every concept, and a concept is unsatisfiable if it is
+//! subsumed by [`vocab::OWL_NOTHING`], or has an existential restriction with an unsatisfiable
+//! filler. An unsatisfiable concept is subsumed by every named concept, and so equivalent to
+//! `owl:Nothing`.
+//!
+//! [`vocab::OWL_THING`]: ../vocab/constant.OWL_THING.html
+//! [`vocab::OWL_NOTHING`]: ../vocab/constant.OWL_NOTHING.html
+use crate::ontology::{ClassAssertion, Entity};
+use crate::taxonomy::Taxonomy;
+use crate::vocab;
+use cid_fork_rlay::ToCid;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+type CidBytes = Vec<u8>;
+
+/// A class expression of the EL fragment, referring to classes and object properties by CID.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Concept {
+    /// A class expression that is treated as atomic, e.g. a `Class`.
+    Named(CidBytes),
+    /// `ObjectIntersectionOf` of the operands. The empty intersection is `owl:Thing`.
+    Intersection(Vec<Concept>),
+    /// `ObjectSomeValuesFrom` with an object property and a filler.
+    Some(CidBytes, Box<Concept>),
+}
+
+impl Concept {
+    pub fn named(cid: &[u8]) -> Self {
+        Concept::Named(cid.to_vec())
+    }
+
+    pub fn some(property: &[u8], filler: Concept) -> Self {
+        Concept::Some(property.to_vec(), Box::new(filler))
+    }
+}
+
+/// Normalized axioms over concept and role ids.
+#[derive(Debug, Clone, Default)]
+struct Axioms {
+    /// `A ⊑ B`
+    told: BTreeMap<usize, BTreeSet<usize>>,
+    /// `A1 ⊓ ... ⊓ An ⊑ B`, as (premises, conclusion)
+    conjunctions: Vec<(Vec<usize>, usize)>,
+    /// `A ⊑ ∃r.B`, indexed by `A`
+    existential_rhs: BTreeMap<usize, BTreeSet<(usize, usize)>>,
+    /// `∃r.A ⊑ B`, indexed by `A`
+    existential_lhs: BTreeMap<usize, BTreeSet<(usize, usize)>>,
+    /// `r ⊑ s`
+    roles: BTreeMap<usize, BTreeSet<usize>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Reasoner {
+    /// CIDs of named concepts, indexed by concept id. Fresh concepts introduced during
+    /// normalization have no CID.
+    concepts: Vec<Option<CidBytes>>,
+    concept_ids: BTreeMap<Concept, usize>,
+    role_ids: BTreeMap<CidBytes, usize>,
+    /// Concept ids of individuals, keyed by the CID used as assertion subject.
+    individuals: BTreeMap<CidBytes, usize>,
+    /// Asserted (individual, class) pairs.
+    class_assertions: BTreeSet<(CidBytes, CidBytes)>,
+    axioms: Axioms,
+}
+
+impl Reasoner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects the axioms of all supported entities in `entities`, ignoring all other kinds.
+    pub fn from_entities<'a, I: IntoIterator<Item = &'a Entity>>(entities: I) -> Self {
+        let mut reasoner = Self::new();
+        for entity in entities {
+            reasoner.insert_entity(entity);
+        }
+        reasoner
+    }
+
+    /// Collects the axioms of the entity, if it is of a supported kind. Returns whether it was.
+    pub fn insert_entity(&mut self, entity: &Entity) -> bool {
+        let super_classes = match entity {
+            Entity::Class(ent) => &ent.super_class_expression,
+            Entity::ObjectIntersectionOf(ent) => &ent.super_class_expression,
+            Entity::ObjectSomeValuesFrom(ent) => &ent.super_class_expression,
+            Entity::ObjectProperty(ent) => {
+                let cid = entity.to_bytes();
+                self.role_id(&cid);
+                for sup in ent.super_object_property_expression.iter() {
+                    self.add_subproperty_of(&cid, sup);
+                }
+                return true;
+            }
+            Entity::ClassAssertion(ent) => match ent.subject {
+                Some(ref subject) => {
+                    self.add_class_assertion(subject, &Concept::named(&ent.class));
+                    return true;
+                }
+                None => return false,
+            },
+            Entity::ObjectPropertyAssertion(ent) => {
+                match (&ent.subject, &ent.property, &ent.target) {
+                    (Some(subject), Some(property), Some(target)) => {
+                        self.add_object_property_assertion(subject, property, target);
+                        return true;
+                    }
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        };
+
+        let cid = entity.to_bytes();
+        self.concept_id(&Concept::Named(cid.clone()));
+        for sup in super_classes.iter() {
+            self.add_subclass_of(&Concept::Named(cid.clone()), &Concept::Named(sup.clone()));
+        }
+        true
+    }
+
+    /// Adds the axiom `sub ⊑ sup`.
+    pub fn add_subclass_of(&mut self, sub: &Concept, sup: &Concept) {
+        let sub_id = self.concept_id(sub);
+        let sup_id = self.concept_id(sup);
+        self.add_told(sub_id, sup_id);
+    }
+
+    /// Adds the axiom `sub ⊑ sup` between two object properties.
+    pub fn add_subproperty_of(&mut self, sub: &[u8], sup: &[u8]) {
+        let sub_id = self.role_id(sub);
+        let sup_id = self.role_id(sup);
+        self.axioms.roles.entry(sub_id).or_default().insert(sup_id);
+    }
+
+    /// Defines the class expression with the CID `cid` to be equivalent to `concept`, e.g. to
+    /// provide the operands of an `ObjectIntersectionOf` entity.
+    pub fn define(&mut self, cid: &[u8], concept: &Concept) {
+        let named = Concept::named(cid);
+        self.add_subclass_of(&named, concept);
+        self.add_subclass_of(concept, &named);
+    }
+
+    /// Asserts the individual to be an instance of the concept.
+    pub fn add_class_assertion(&mut self, individual: &[u8], class: &Concept) {
+        let individual_id = self.individual_id(individual);
+        let class_id = self.concept_id(class);
+        self.add_told(individual_id, class_id);
+        if let Concept::Named(class) = class {
+            self.class_assertions
+                .insert((individual.to_vec(), class.clone()));
+        }
+    }
+
+    /// Asserts the subject individual to be related to the target individual via the property.
+    pub fn add_object_property_assertion(
+        &mut self,
+        subject: &[u8],
+        property: &[u8],
+        target: &[u8],
+    ) {
+        let subject_id = self.individual_id(subject);
+        let role = self.role_id(property);
+        let target_id = self.individual_id(target);
+        self.axioms
+            .existential_rhs
+            .entry(subject_id)
+            .or_default()
+            .insert((role, target_id));
+    }
+
+    fn individual_id(&mut self, cid: &[u8]) -> usize {
+        if let Some(id) = self.individuals.get(cid) {
+            return *id;
+        }
+        let id = self.concepts.len();
+        self.concepts.push(None);
+        self.individuals.insert(cid.to_vec(), id);
+        id
+    }
+
+    fn add_told(&mut self, sub: usize, sup: usize) {
+        self.axioms.told.entry(sub).or_default().insert(sup);
+    }
+
+    fn role_id(&mut self, cid: &[u8]) -> usize {
+        let next = self.role_ids.len();
+        *self.role_ids.entry(cid.to_vec()).or_insert(next)
+    }
+
+    /// Returns the id of a concept, normalizing complex concepts into fresh concepts that are
+    /// equivalent to them.
+    fn concept_id(&mut self, concept: &Concept) -> usize {
+        if let Some(id) = self.concept_ids.get(concept) {
+            return *id;
+        }
+
+        let id = self.concepts.len();
+        match concept {
+            Concept::Named(cid) => self.concepts.push(Some(cid.clone())),
+            Concept::Intersection(operands) => {
+                self.concepts.push(None);
+                let operand_ids: Vec<usize> =
+                    operands.iter().map(|op| self.concept_id(op)).collect();
+                for operand_id in operand_ids.iter() {
+                    self.add_told(id, *operand_id);
+                }
+                self.axioms.conjunctions.push((operand_ids, id));
+            }
+            Concept::Some(property, filler) => {
+                self.concepts.push(None);
+                let role = self.role_id(property);
+                let filler_id = self.concept_id(filler);
+                self.axioms
+                    .existential_rhs
+                    .entry(id)
+                    .or_default()
+                    .insert((role, filler_id));
+                self.axioms
+                    .existential_lhs
+                    .entry(filler_id)
+                    .or_default()
+                    .insert((role, id));
+            }
+        }
+        self.concept_ids.insert(concept.clone(), id);
+        id
+    }
+
+    /// Reflexive-transitive closure of the object property hierarchy.
+    fn super_roles(&self) -> Vec<BTreeSet<usize>> {
+        (0..self.role_ids.len())
+            .map(|role| {
+                let mut supers = BTreeSet::new();
+                let mut stack = vec![role];
+                while let Some(current) = stack.pop() {
+                    if !supers.insert(current) {
+                        continue;
+                    }
+                    if let Some(direct) = self.axioms.roles.get(&current) {
+                        stack.extend(direct.iter().cloned());
+                    }
+                }
+                supers
+            })
+            .collect()
+    }
+
+    /// Concept id of `owl:Thing` or `owl:Nothing`, if it occurs in the axioms.
+    fn well_known_id(&self, cid: &[u8]) -> Option<usize> {
+        self.concept_ids.get(&Concept::named(cid)).cloned()
+    }
+
+    /// Saturates the axioms and returns the subsumers of every concept id.
+    fn saturate(&self) -> Vec<BTreeSet<usize>> {
+        let axioms = &self.axioms;
+        let super_roles = self.super_roles();
+        let concept_count = self.concepts.len();
+        let top = self.well_known_id(vocab::OWL_THING);
+        let bottom = self.well_known_id(vocab::OWL_NOTHING);
+
+        let mut subsumers: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); concept_count];
+        let mut links: BTreeSet<(usize, usize, usize)> = BTreeSet::new();
+        // (role, predecessor) for every link ending in a concept
+        let mut predecessors: Vec<Vec<(usize, usize)>> = vec![Vec::new(); concept_count];
+
+        let mut queue: VecDeque<Inference> = VecDeque::new();
+        for id in 0..concept_count {
+            queue.push_back(Inference::Subsumer(id, id));
+            if let Some(top) = top {
+                queue.push_back(Inference::Subsumer(id, top));
+            }
+            for (premises, conclusion) in axioms.conjunctions.iter() {
+                if premises.is_empty() {
+                    queue.push_back(Inference::Subsumer(id, *conclusion));
+                }
+            }
+        }
+
+        while let Some(inference) = queue.pop_front() {
+            match inference {
+                Inference::Subsumer(sub, sup) => {
+                    if !subsumers[sub].insert(sup) {
+                        continue;
+                    }
+                    if Some(sup) == bottom {
+                        // everything linked to an unsatisfiable concept is unsatisfiable
+                        for (_, predecessor) in predecessors[sub].iter() {
+                            queue.push_back(Inference::Subsumer(*predecessor, sup));
+                        }
+                    }
+                    if let Some(told) = axioms.told.get(&sup) {
+                        for next in told.iter() {
+                            queue.push_back(Inference::Subsumer(sub, *next));
+                        }
+                    }
+                    for (premises, conclusion) in axioms.conjunctions.iter() {
+                        if premises.contains(&sup)
+                            && premises.iter().all(|p| subsumers[sub].contains(p))
+                        {
+                            queue.push_back(Inference::Subsumer(sub, *conclusion));
+                        }
+                    }
+                    if let Some(existentials) = axioms.existential_rhs.get(&sup) {
+                        for (role, filler) in existentials.iter() {
+                            queue.push_back(Inference::Link(sub, *role, *filler));
+                        }
+                    }
+                    if let Some(existentials) = axioms.existential_lhs.get(&sup) {
+                        for (link_role, predecessor) in predecessors[sub].iter() {
+                            for (role, conclusion) in existentials.iter() {
+                                if super_roles[*link_role].contains(role) {
+                                    queue.push_back(Inference::Subsumer(*predecessor, *conclusion));
+                                }
+                            }
+                        }
+                    }
+                }
+                Inference::Link(sub, link_role, filler) => {
+                    if !links.insert((sub, link_role, filler)) {
+                        continue;
+                    }
+                    predecessors[filler].push((link_role, sub));
+                    if let Some(bottom) = bottom.filter(|bottom| subsumers[filler].contains(bottom))
+                    {
+                        queue.push_back(Inference::Subsumer(sub, bottom));
+                    }
+                    for sup in subsumers[filler].iter() {
+                        if let Some(existentials) = axioms.existential_lhs.get(sup) {
+                            for (role, conclusion) in existentials.iter() {
+                                if super_roles[link_role].contains(role) {
+                                    queue.push_back(Inference::Subsumer(sub, *conclusion));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        subsumers
+    }
+
+    /// Returns the CIDs of the named concepts among the subsumers, without `cid` itself. An
+    /// unsatisfiable concept is subsumed by all named concepts.
+    fn named_subsumers(&self, subsumers: &BTreeSet<usize>, cid: &[u8]) -> BTreeSet<CidBytes> {
+        let unsatisfiable = match self.well_known_id(vocab::OWL_NOTHING) {
+            Some(bottom) => subsumers.contains(&bottom),
+            None => false,
+        };
+        let named: Vec<&CidBytes> = match unsatisfiable {
+            true => self.concepts.iter().flatten().collect(),
+            false => subsumers
+                .iter()
+                .filter_map(|sup| self.concepts[*sup].as_ref())
+                .collect(),
+        };
+        named
+            .into_iter()
+            .filter(|sup| sup.as_slice() != cid)
+            .cloned()
+            .collect()
+    }
+
+    /// Saturates the axioms and returns the inferred subsumptions between named concepts.
+    pub fn classify(&self) -> Classification {
+        let subsumers = self.saturate();
+
+        let mut classification = Classification::default();
+        for (id, cid) in self.concepts.iter().enumerate() {
+            let cid = match cid {
+                Some(cid) => cid,
+                None => continue,
+            };
+            let named_subsumers = self.named_subsumers(&subsumers[id], cid);
+            classification
+                .subsumers
+                .insert(cid.clone(), named_subsumers);
+        }
+        classification
+    }
+
+    /// Saturates the axioms and returns the inferred types of all individuals.
+    pub fn realize(&self) -> Realization {
+        let subsumers = self.saturate();
+
+        let mut realization = Realization::default();
+        for (individual, id) in self.individuals.iter() {
+            let types = self.named_subsumers(&subsumers[*id], individual);
+            for class in types.iter() {
+                let pair = (individual.clone(), class.clone());
+                if !self.class_assertions.contains(&pair) {
+                    realization.inferred.insert(pair);
+                }
+            }
+            realization.types.insert(individual.clone(), types);
+        }
+        realization
+    }
+}
+
+enum Inference {
+    /// The second concept subsumes the first one.
+    Subsumer(usize, usize),
+    /// The first concept is linked to the filler via the role.
+    Link(usize, usize, usize),
+}
+
+/// Inferred class hierarchy, keyed by CID.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Classification {
+    subsumers: BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+}
+
+impl Classification {
+    /// All named concepts that were classified.
+    pub fn concepts(&self) -> impl Iterator<Item = &CidBytes> {
+        self.subsumers.keys()
+    }
+
+    /// Named concepts that subsume the concept, not including the concept itself.
+    pub fn subsumers(&self, cid: &[u8]) -> Option<&BTreeSet<CidBytes>> {
+        self.subsumers.get(cid)
+    }
+
+    /// Whether `sub` is inferred to be subsumed by `sup`. Every concept subsumes itself.
+    pub fn is_subsumed_by(&self, sub: &[u8], sup: &[u8]) -> bool {
+        match self.subsumers.get(sub) {
+            Some(subsumers) => sub == sup || subsumers.contains(sup),
+            None => false,
+        }
+    }
+
+    /// Whether the concept is inferred to be subsumed by `owl:Nothing`.
+    pub fn is_unsatisfiable(&self, cid: &[u8]) -> bool {
+        self.is_subsumed_by(cid, vocab::OWL_NOTHING)
+    }
+
+    /// Named concepts that are inferred to be equivalent to the concept.
+    pub fn equivalents(&self, cid: &[u8]) -> BTreeSet<CidBytes> {
+        match self.subsumers.get(cid) {
+            Some(subsumers) => subsumers
+                .iter()
+                .filter(|sup| self.is_subsumed_by(sup, cid))
+                .cloned()
+                .collect(),
+            None => BTreeSet::new(),
+        }
+    }
+
+    /// The concept representing the equivalence class of the concept in [`to_taxonomy`]: the one
+    /// with the smallest CID among the concept and its equivalents.
+    ///
+    /// [`to_taxonomy`]: #method.to_taxonomy
+    pub fn representative(&self, cid: &[u8]) -> CidBytes {
+        self.equivalents(cid)
+            .into_iter()
+            .chain(std::iter::once(cid.to_vec()))
+            .min()
+            .unwrap()
+    }
+
+    /// Builds a [`Taxonomy`] from the inferred subsumptions, with only direct subsumptions as
+    /// edges. Equivalent concepts are collapsed into their [`representative`].
+    ///
+    /// [`representative`]: #method.representative
+    pub fn to_taxonomy(&self) -> Taxonomy {
+        let representatives: BTreeMap<&CidBytes, CidBytes> = self
+            .concepts()
+            .map(|concept| (concept, self.representative(concept)))
+            .collect();
+
+        let mut taxonomy = Taxonomy::new();
+        for (sub, subsumers) in self.subsumers.iter() {
+            if representatives[sub] != *sub {
+                continue;
+            }
+            let strict: BTreeSet<&CidBytes> = subsumers
+                .iter()
+                .filter(|sup| !self.is_subsumed_by(sup, sub))
+                .map(|sup| representatives.get(sup).unwrap_or(sup))
+                .collect();
+            for sup in strict.iter() {
+                // direct if no other strict subsumer lies between the two
+                let direct = !strict
+                    .iter()
+                    .any(|other| other != sup && self.is_subsumed_by(other, sup));
+                if direct {
+                    taxonomy.add_edge(sub, sup);
+                }
+            }
+        }
+        taxonomy
+    }
+}
+
+/// A `ClassAssertion` that was inferred instead of asserted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferredClassAssertion {
+    pub cid: CidBytes,
+    pub assertion: ClassAssertion,
+}
+
+/// Asserted and inferred types of individuals, keyed by CID.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Realization {
+    types: BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+    /// (individual, class) pairs that were inferred but not asserted.
+    inferred: BTreeSet<(CidBytes, CidBytes)>,
+}
+
+impl Realization {
+    /// All individuals that were realized.
+    pub fn individuals(&self) -> impl Iterator<Item = &CidBytes> {
+        self.types.keys()
+    }
+
+    /// Asserted and inferred named types of the individual.
+    pub fn types(&self, individual: &[u8]) -> Option<&BTreeSet<CidBytes>> {
+        self.types.get(individual)
+    }
+
+    pub fn is_instance_of(&self, individual: &[u8], class: &[u8]) -> bool {
+        match self.types.get(individual) {
+            Some(types) => types.contains(class),
+            None => false,
+        }
+    }
+
+    /// Whether the type of the individual was inferred, as opposed to asserted.
+    pub fn is_inferred(&self, individual: &[u8], class: &[u8]) -> bool {
+        self.inferred
+            .contains(&(individual.to_vec(), class.to_vec()))
+    }
+
+    /// Class assertions for all inferred types that were not asserted, ordered by individual
+    /// and class.
+    pub fn inferred_class_assertions(&self) -> Vec<InferredClassAssertion> {
+        self.inferred
+            .iter()
+            .map(|(individual, class)| {
+                let assertion = ClassAssertion {
+                    annotations: vec![],
+                    subject: Some(individual.clone()),
+                    class: class.clone(),
+                };
+                InferredClassAssertion {
+                    cid: assertion.to_cid().unwrap().to_bytes(),
+                    assertion,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,159 @@
+use super::{decode, encode, kind_prefix, write_atomic, EntityIter, EntityStore, StoreError};
+use crate::ontology::{Entity, EntityKind};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+type CidBytes = Vec<u8>;
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Lowercase RFC 4648 base32 without padding.
+fn encode_base32(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[(buffer >> bits) as usize & 31] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[(buffer << (5 - bits)) as usize & 31] as char);
+    }
+    encoded
+}
+
+/// Inverse of `encode_base32`, rejecting strings it would not produce.
+fn decode_base32(encoded: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in encoded.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|a| *a == c)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    match bits < 5 && buffer == 0 {
+        true => Some(bytes),
+        false => None,
+    }
+}
+
+/// Stores each entity in its own file, named by the base32-encoded CID, in a directory named by
+/// the last two characters of that name:
+///
+/// ```text
+/// <root>/<last two characters>/<base32 CID>
+/// ```
+///
+/// Entities are written to a temporary file in the same directory first, which is then renamed,
+/// so a crash never leaves a partially written entity behind.
+#[derive(Debug, Clone)]
+pub struct FsStore {
+    root: PathBuf,
+}
+
+impl FsStore {
+    /// Opens the store in the directory, creating the directory if it doesn't exist.
+    pub fn open<P: AsRef<Path>>(root: P) -> Result<Self, StoreError> {
+        fs::create_dir_all(root.as_ref())?;
+        Ok(FsStore {
+            root: root.as_ref().to_path_buf(),
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path of the file the entity with the CID is stored in.
+    pub fn path(&self, cid: &[u8]) -> PathBuf {
+        let name = encode_base32(cid);
+        let shard = &name[name.len().saturating_sub(2)..];
+        self.root.join(shard).join(&name)
+    }
+
+    /// CIDs of all stored entities, sorted.
+    fn cids(&self) -> Result<Vec<CidBytes>, StoreError> {
+        let mut cids = Vec::new();
+        for shard in fs::read_dir(&self.root)? {
+            let shard = shard?;
+            if !shard.file_type()?.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(shard.path())? {
+                // temporary files start with a dot, which is not a base32 character
+                if let Some(cid) = file?.file_name().to_str().and_then(decode_base32) {
+                    cids.push(cid);
+                }
+            }
+        }
+        cids.sort();
+        Ok(cids)
+    }
+}
+
+impl EntityStore for FsStore {
+    fn put(&mut self, entity: &Entity) -> Result<CidBytes, StoreError> {
+        let cid = entity.to_bytes();
+        let path = self.path(&cid);
+        if path.exists() {
+            return Ok(cid);
+        }
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        write_atomic(&path, &encode(entity))?;
+        Ok(cid)
+    }
+
+    fn get(&self, cid: &[u8]) -> Result<Option<Entity>, StoreError> {
+        let bytes = match fs::read(self.path(cid)) {
+            Ok(bytes) => bytes,
+            Err(ref error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        decode(cid, &bytes).map(Some)
+    }
+
+    fn has(&self, cid: &[u8]) -> Result<bool, StoreError> {
+        Ok(self.path(cid).is_file())
+    }
+
+    fn delete(&mut self, cid: &[u8]) -> Result<bool, StoreError> {
+        match fs::remove_file(self.path(cid)) {
+            Ok(()) => Ok(true),
+            Err(ref error) if error.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Entities are yielded in the order of their CIDs. Only the CIDs are listed upfront; each
+    /// entity is read and verified when it is reached.
+    fn iter_kind(&self, kind: EntityKind) -> EntityIter {
+        let prefix = kind_prefix(kind);
+        let cids = match self.cids() {
+            Ok(cids) => cids,
+            Err(error) => return Box::new(std::iter::once(Err(error))),
+        };
+        Box::new(
+            cids.into_iter()
+                .filter(move |cid| cid.starts_with(&prefix))
+                .filter_map(move |cid| match self.get(&cid) {
+                    Ok(Some(entity)) => Some(Ok((cid, entity))),
+                    // deleted since listing
+                    Ok(None) => None,
+                    Err(error) => Some(Err(error)),
+                }),
+        )
+    }
+}
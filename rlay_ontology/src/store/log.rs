@@ -0,0 +1,350 @@
+use super::{decode, encode, kind_prefix, write_atomic, EntityIter, EntityStore, StoreError};
+use crate::ontology::v0::EntityV0;
+use crate::ontology::{Entity, EntityKind};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+type CidBytes = Vec<u8>;
+
+/// Length and checksum, each a little-endian u32.
+const HEADER_LEN: u64 = 8;
+/// The body is the v0 encoding of the stored entity.
+const TAG_PUT: u8 = 0;
+/// The body is the CID of the deleted entity.
+const TAG_DELETE: u8 = 1;
+
+/// CRC-32 (IEEE 802.3).
+fn crc32(bytes: &[u8]) -> u32 {
+    crc32_update(0, bytes)
+}
+
+/// Continues the CRC-32 of the preceding bytes over further bytes.
+fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xedb8_8320,
+                _ => crc >> 1,
+            };
+        }
+    }
+    !crc
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    let mut buf = [0u8; 4];
+    buf.copy_from_slice(&bytes[..4]);
+    u32::from_le_bytes(buf)
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&bytes[..8]);
+    u64::from_le_bytes(buf)
+}
+
+/// Reads the tag and body of the record at the offset.
+///
+/// Returns `None` for a torn tail, as left by an interrupted write: a record that runs past
+/// `log_len`, or a last record with an invalid checksum. An invalid checksum of a record that is
+/// followed by further data is an error.
+fn read_record(
+    mut file: &File,
+    offset: u64,
+    log_len: u64,
+) -> Result<Option<(u8, Vec<u8>)>, StoreError> {
+    if offset + HEADER_LEN > log_len {
+        return Ok(None);
+    }
+    let mut header = [0u8; HEADER_LEN as usize];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut header)?;
+    let length = u64::from(read_u32(&header));
+    let end = offset + HEADER_LEN + length;
+    if end > log_len {
+        return Ok(None);
+    }
+    let mut record = vec![0u8; length as usize];
+    file.read_exact(&mut record)?;
+    if length == 0 || crc32(&record) != read_u32(&header[4..]) {
+        return match end == log_len {
+            true => Ok(None),
+            false => Err(StoreError::InvalidRecord { offset }),
+        };
+    }
+    let body = record.split_off(1);
+    Ok(Some((record[0], body)))
+}
+
+/// Continues the CRC-32 of the log before `from` over the bytes up to `to`.
+fn checksum_range(mut file: &File, from: u64, to: u64, mut crc: u32) -> Result<u32, StoreError> {
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut remaining = to - from;
+    file.seek(SeekFrom::Start(from))?;
+    while remaining > 0 {
+        let chunk = std::cmp::min(remaining, buf.len() as u64) as usize;
+        file.read_exact(&mut buf[..chunk])?;
+        crc = crc32_update(crc, &buf[..chunk]);
+        remaining -= chunk as u64;
+    }
+    Ok(crc)
+}
+
+/// Serialized index: the covered log length, the CRC-32 of the covered part of the log, the
+/// entries as (CID length, CID, offset), and a checksum of all of it.
+fn encode_index(log_len: u64, log_checksum: u32, index: &BTreeMap<CidBytes, u64>) -> Vec<u8> {
+    let mut bytes = log_len.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&log_checksum.to_le_bytes());
+    for (cid, offset) in index.iter() {
+        bytes.extend_from_slice(&(cid.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(cid);
+        bytes.extend_from_slice(&offset.to_le_bytes());
+    }
+    let checksum = crc32(&bytes);
+    bytes.extend_from_slice(&checksum.to_le_bytes());
+    bytes
+}
+
+/// Inverse of `encode_index`. Returns `None` if the index is damaged.
+fn decode_index(bytes: &[u8]) -> Option<(u64, u32, BTreeMap<CidBytes, u64>)> {
+    if bytes.len() < 16 {
+        return None;
+    }
+    let (bytes, checksum) = bytes.split_at(bytes.len() - 4);
+    if crc32(bytes) != read_u32(checksum) {
+        return None;
+    }
+    let log_len = read_u64(bytes);
+    let log_checksum = read_u32(&bytes[8..]);
+    let mut index = BTreeMap::new();
+    let mut rest = &bytes[12..];
+    while !rest.is_empty() {
+        if rest.len() < 4 {
+            return None;
+        }
+        let cid_len = read_u32(rest) as usize;
+        if rest.len() < 4 + cid_len + 8 {
+            return None;
+        }
+        let cid = rest[4..(4 + cid_len)].to_vec();
+        index.insert(cid, read_u64(&rest[(4 + cid_len)..]));
+        rest = &rest[(4 + cid_len + 8)..];
+    }
+    Some((log_len, log_checksum, index))
+}
+
+/// Appends every change to a single log file, which makes the log an audit trail of the store.
+///
+/// Each record consists of the length and CRC-32 checksum of its body, followed by the body: a
+/// tag byte and either the v0 encoding of a stored entity, or the CID of a deleted one. Records
+/// are synced to disk before a write returns.
+///
+/// The index from CIDs to the offsets of their records is kept in memory, and can be saved next to
+/// the log with [`checkpoint`](#method.checkpoint), together with a checksum of the part of the
+/// log it covers. On opening, the saved index is loaded and the records appended after it are
+/// replayed; without a usable saved index, or if the covered part of the log no longer matches its
+/// checksum, the whole log is replayed. A record that was only partially written before a crash at the end of the log is
+/// truncated away; a damaged record elsewhere makes opening fail, and leaves the log untouched.
+#[derive(Debug)]
+pub struct LogStore {
+    path: PathBuf,
+    file: File,
+    /// Offsets of the records of the stored entities.
+    index: BTreeMap<CidBytes, u64>,
+    /// Length of the valid part of the log.
+    len: u64,
+    /// CRC-32 of the valid part of the log.
+    checksum: u32,
+    truncated: u64,
+}
+
+impl LogStore {
+    /// Opens the log file, creating it if it doesn't exist.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path.as_ref())?;
+        let mut store = LogStore {
+            path: path.as_ref().to_path_buf(),
+            file,
+            index: BTreeMap::new(),
+            len: 0,
+            checksum: 0,
+            truncated: 0,
+        };
+
+        let log_len = store.file.metadata()?.len();
+        let saved = match std::fs::read(store.index_path()) {
+            Ok(bytes) => decode_index(&bytes),
+            Err(_) => None,
+        };
+        if let Some((covered, checksum, index)) = saved {
+            if covered <= log_len && checksum_range(&store.file, 0, covered, 0)? == checksum {
+                store.index = index;
+                store.len = covered;
+                store.checksum = checksum;
+            }
+        }
+        store.replay()?;
+        Ok(store)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path the index is saved to by [`checkpoint`](#method.checkpoint).
+    pub fn index_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".index");
+        PathBuf::from(path)
+    }
+
+    /// Number of bytes of partially written records that were truncated from the end of the log
+    /// when it was opened.
+    pub fn truncated(&self) -> u64 {
+        self.truncated
+    }
+
+    /// Saves the index, so that the next [`open`](#method.open) only has to replay the records
+    /// appended after this point.
+    pub fn checkpoint(&self) -> Result<(), StoreError> {
+        let index = encode_index(self.len, self.checksum, &self.index);
+        write_atomic(&self.index_path(), &index)?;
+        Ok(())
+    }
+
+    /// Discards the index and rebuilds it by replaying the whole log.
+    pub fn rebuild_index(&mut self) -> Result<(), StoreError> {
+        self.index.clear();
+        self.len = 0;
+        self.checksum = 0;
+        self.replay()
+    }
+
+    /// All stored entities, in the order they were stored in.
+    pub fn iter(&self) -> EntityIter {
+        self.in_insertion_order(|_| true)
+    }
+
+    /// Applies the records after the indexed part of the log to the index, and truncates a torn
+    /// tail after the last complete record.
+    fn replay(&mut self) -> Result<(), StoreError> {
+        let log_len = self.file.metadata()?.len();
+        let mut offset = self.len;
+        while let Some((tag, body)) = read_record(&self.file, offset, log_len)? {
+            match tag {
+                TAG_PUT => {
+                    let entity: Entity = EntityV0::deserialize_strict(&body)
+                        .map_err(|_| StoreError::InvalidRecord { offset })?
+                        .into();
+                    self.index.insert(entity.to_bytes(), offset);
+                }
+                TAG_DELETE => {
+                    self.index.remove(&body);
+                }
+                _ => return Err(StoreError::InvalidRecord { offset }),
+            }
+            offset += HEADER_LEN + 1 + body.len() as u64;
+        }
+
+        if offset < log_len {
+            self.file.set_len(offset)?;
+            self.file.sync_all()?;
+            self.truncated += log_len - offset;
+        }
+        self.checksum = checksum_range(&self.file, self.len, offset, self.checksum)?;
+        self.len = offset;
+        Ok(())
+    }
+
+    /// Appends a record, and returns its offset.
+    fn append(&mut self, tag: u8, body: &[u8]) -> Result<u64, StoreError> {
+        let mut record = Vec::with_capacity(HEADER_LEN as usize + 1 + body.len());
+        record.extend_from_slice(&(1 + body.len() as u32).to_le_bytes());
+        record.extend_from_slice(&[0; 4]);
+        record.push(tag);
+        record.extend_from_slice(body);
+        let checksum = crc32(&record[(HEADER_LEN as usize)..]);
+        record[4..8].copy_from_slice(&checksum.to_le_bytes());
+
+        let offset = self.len;
+        let written = self
+            .file
+            .write_all(&record)
+            .and_then(|_| self.file.sync_data());
+        if let Err(error) = written {
+            // drop a partially written record, so that the next one starts at a record boundary
+            let _ = self.file.set_len(offset);
+            return Err(error.into());
+        }
+        self.len += record.len() as u64;
+        self.checksum = crc32_update(self.checksum, &record);
+        Ok(offset)
+    }
+
+    fn read_entity(&self, cid: &[u8], offset: u64) -> Result<Entity, StoreError> {
+        match read_record(&self.file, offset, self.len)? {
+            Some((TAG_PUT, body)) => decode(cid, &body),
+            _ => Err(StoreError::InvalidRecord { offset }),
+        }
+    }
+
+    fn in_insertion_order<'a, F: Fn(&[u8]) -> bool>(&'a self, filter: F) -> EntityIter<'a> {
+        let mut entries: Vec<(u64, &CidBytes)> = self
+            .index
+            .iter()
+            .filter(|(cid, _)| filter(cid))
+            .map(|(cid, offset)| (*offset, cid))
+            .collect();
+        entries.sort();
+        Box::new(entries.into_iter().map(move |(offset, cid)| {
+            self.read_entity(cid, offset)
+                .map(|entity| (cid.clone(), entity))
+        }))
+    }
+}
+
+impl EntityStore for LogStore {
+    fn put(&mut self, entity: &Entity) -> Result<CidBytes, StoreError> {
+        let cid = entity.to_bytes();
+        if !self.index.contains_key(&cid) {
+            let offset = self.append(TAG_PUT, &encode(entity))?;
+            self.index.insert(cid.clone(), offset);
+        }
+        Ok(cid)
+    }
+
+    fn get(&self, cid: &[u8]) -> Result<Option<Entity>, StoreError> {
+        match self.index.get(cid) {
+            Some(offset) => self.read_entity(cid, *offset).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn has(&self, cid: &[u8]) -> Result<bool, StoreError> {
+        Ok(self.index.contains_key(cid))
+    }
+
+    /// Appends a deletion record; the stored entity stays in the log.
+    fn delete(&mut self, cid: &[u8]) -> Result<bool, StoreError> {
+        if !self.index.contains_key(cid) {
+            return Ok(false);
+        }
+        self.append(TAG_DELETE, cid)?;
+        self.index.remove(cid);
+        Ok(true)
+    }
+
+    /// Entities are yielded in the order they were stored in.
+    fn iter_kind(&self, kind: EntityKind) -> EntityIter {
+        let prefix = kind_prefix(kind);
+        self.in_insertion_order(move |cid| cid.starts_with(&prefix))
+    }
+}
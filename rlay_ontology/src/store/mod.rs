@@ -0,0 +1,176 @@
+//! Persistent storage of entities, addressed by their CIDs.
+//!
+//! The persistent backends store entities in the canonical v0 encoding, and verify on read that
+//! the stored bytes decode to an entity with the requested CID.
+mod fs;
+mod log;
+
+pub use self::fs::FsStore;
+pub use self::log::LogStore;
+
+use crate::ontology::canonical::{read_varint_strict, CanonicalError};
+use crate::ontology::v0::EntityV0;
+use crate::ontology::{Entity, EntityKind};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+type CidBytes = Vec<u8>;
+
+#[derive(Debug)]
+pub enum StoreError {
+    Io(io::Error),
+    /// The stored bytes are not a canonical v0-encoded entity.
+    Decode {
+        cid: CidBytes,
+        error: CanonicalError,
+    },
+    /// The stored entity does not have the CID it is stored under.
+    CidMismatch {
+        expected: CidBytes,
+        actual: CidBytes,
+    },
+    /// A record in the middle of the log is damaged, or could not be interpreted.
+    InvalidRecord {
+        offset: u64,
+    },
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StoreError::Io(error) => write!(f, "{}", error),
+            StoreError::Decode { error, .. } => write!(f, "invalid stored entity: {}", error),
+            StoreError::CidMismatch { .. } => {
+                write!(f, "stored entity does not match its CID")
+            }
+            StoreError::InvalidRecord { offset } => {
+                write!(f, "invalid log record at offset {}", offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StoreError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for StoreError {
+    fn from(error: io::Error) -> Self {
+        StoreError::Io(error)
+    }
+}
+
+/// Iterator over stored entities and their CIDs.
+pub type EntityIter<'a> = Box<dyn Iterator<Item = Result<(CidBytes, Entity), StoreError>> + 'a>;
+
+pub trait EntityStore {
+    /// Stores the entity, and returns its CID. Storing an entity that is already present does
+    /// nothing.
+    fn put(&mut self, entity: &Entity) -> Result<CidBytes, StoreError>;
+
+    fn get(&self, cid: &[u8]) -> Result<Option<Entity>, StoreError>;
+
+    fn has(&self, cid: &[u8]) -> Result<bool, StoreError>;
+
+    /// Removes the entity, and returns whether it was present.
+    fn delete(&mut self, cid: &[u8]) -> Result<bool, StoreError>;
+
+    /// All stored entities of the kind.
+    fn iter_kind(&self, kind: EntityKind) -> EntityIter;
+}
+
+impl EntityStore for BTreeMap<CidBytes, Entity> {
+    fn put(&mut self, entity: &Entity) -> Result<CidBytes, StoreError> {
+        let cid = entity.to_bytes();
+        self.entry(cid.clone()).or_insert_with(|| entity.clone());
+        Ok(cid)
+    }
+
+    fn get(&self, cid: &[u8]) -> Result<Option<Entity>, StoreError> {
+        Ok(BTreeMap::get(self, cid).cloned())
+    }
+
+    fn has(&self, cid: &[u8]) -> Result<bool, StoreError> {
+        Ok(self.contains_key(cid))
+    }
+
+    fn delete(&mut self, cid: &[u8]) -> Result<bool, StoreError> {
+        Ok(self.remove(cid).is_some())
+    }
+
+    fn iter_kind(&self, kind: EntityKind) -> EntityIter {
+        Box::new(
+            self.iter()
+                .filter(move |(_, entity)| entity.kind() == kind)
+                .map(|(cid, entity)| Ok((cid.clone(), entity.clone()))),
+        )
+    }
+}
+
+/// The v0 encoding of the entity.
+fn encode(entity: &Entity) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    Into::<EntityV0>::into(entity.clone())
+        .serialize(&mut bytes)
+        .unwrap();
+    bytes
+}
+
+/// Decodes the v0-encoded entity stored under `cid`, and checks that it has that CID.
+fn decode(cid: &[u8], bytes: &[u8]) -> Result<Entity, StoreError> {
+    let entity: Entity = EntityV0::deserialize_strict(bytes)
+        .map_err(|error| StoreError::Decode {
+            cid: cid.to_vec(),
+            error,
+        })?
+        .into();
+    let actual = entity.to_bytes();
+    match actual.as_slice() == cid {
+        true => Ok(entity),
+        false => Err(StoreError::CidMismatch {
+            expected: cid.to_vec(),
+            actual,
+        }),
+    }
+}
+
+/// The version and codec header shared by the CIDs of all entities of the kind.
+fn kind_prefix(kind: EntityKind) -> CidBytes {
+    let cid = kind.empty_entity().to_bytes();
+    let (_, version_len) = read_varint_strict(&cid).unwrap();
+    let (_, codec_len) = read_varint_strict(&cid[version_len..]).unwrap();
+    cid[..(version_len + codec_len)].to_vec()
+}
+
+/// Distinguishes the temporary files of concurrent writes within the process.
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Writes the file via a temporary file in the same directory, which is renamed to `path` once it
+/// is complete, so that a crash never leaves a partially written file behind.
+fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let temp_path = path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        path.file_name().unwrap().to_string_lossy(),
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    let written = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(bytes)?;
+            file.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&temp_path, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    written
+}
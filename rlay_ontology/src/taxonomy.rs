@@ -0,0 +1,164 @@
+//! Subclass hierarchy over the asserted `superClassExpression`s of `Class` entities.
+//!
+//! Nodes of the hierarchy are CIDs: the CIDs of inserted classes, as well as the CIDs of all
+//! class expressions referenced as their superclasses. The transitive closure is maintained on
+//! insertion, so that ancestor and descendant queries don't need to walk the graph.
+use crate::ontology::{Class, Entity};
+use cid_fork_rlay::ToCid;
+use std::collections::{BTreeMap, BTreeSet};
+
+type CidBytes = Vec<u8>;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Taxonomy {
+    parents: BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+    children: BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+    ancestors: BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+    descendants: BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+}
+
+impl Taxonomy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the hierarchy from all `Class` entities in `entities`, ignoring all other kinds.
+    pub fn from_entities<'a, I: IntoIterator<Item = &'a Entity>>(entities: I) -> Self {
+        let mut taxonomy = Self::new();
+        for entity in entities {
+            taxonomy.insert_entity(entity);
+        }
+        taxonomy
+    }
+
+    /// Inserts the entity if it is a `Class`. Returns whether it was inserted.
+    pub fn insert_entity(&mut self, entity: &Entity) -> bool {
+        match entity {
+            Entity::Class(class) => {
+                self.insert(class);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Inserts a class and its asserted superclasses, and returns the CID of the class.
+    pub fn insert(&mut self, class: &Class) -> CidBytes {
+        let cid = class.to_cid().unwrap().to_bytes();
+        self.add_node(&cid);
+        for parent in class.super_class_expression.iter() {
+            self.add_edge(&cid, parent);
+        }
+        cid
+    }
+
+    /// Inserts a single subclass edge between two nodes, identified by their CIDs.
+    pub fn add_edge(&mut self, sub: &[u8], sup: &[u8]) {
+        self.add_node(sub);
+        self.add_node(sup);
+        if !self.parents.get_mut(sub).unwrap().insert(sup.to_vec()) {
+            return;
+        }
+        self.children.get_mut(sup).unwrap().insert(sub.to_vec());
+
+        let mut upper = self.ancestors[sup].clone();
+        upper.insert(sup.to_vec());
+        let mut lower = self.descendants[sub].clone();
+        lower.insert(sub.to_vec());
+
+        for node in lower.iter() {
+            let ancestors = self.ancestors.get_mut(node).unwrap();
+            ancestors.extend(upper.iter().filter(|n| *n != node).cloned());
+        }
+        for node in upper.iter() {
+            let descendants = self.descendants.get_mut(node).unwrap();
+            descendants.extend(lower.iter().filter(|n| *n != node).cloned());
+        }
+    }
+
+    fn add_node(&mut self, cid: &[u8]) {
+        if self.parents.contains_key(cid) {
+            return;
+        }
+        self.parents.insert(cid.to_vec(), BTreeSet::new());
+        self.children.insert(cid.to_vec(), BTreeSet::new());
+        self.ancestors.insert(cid.to_vec(), BTreeSet::new());
+        self.descendants.insert(cid.to_vec(), BTreeSet::new());
+    }
+
+    pub fn contains(&self, cid: &[u8]) -> bool {
+        self.parents.contains_key(cid)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &CidBytes> {
+        self.parents.keys()
+    }
+
+    /// Direct superclasses of a node.
+    pub fn parents(&self, cid: &[u8]) -> Option<&BTreeSet<CidBytes>> {
+        self.parents.get(cid)
+    }
+
+    /// Direct subclasses of a node.
+    pub fn children(&self, cid: &[u8]) -> Option<&BTreeSet<CidBytes>> {
+        self.children.get(cid)
+    }
+
+    /// Transitive superclasses of a node, not including the node itself.
+    pub fn ancestors(&self, cid: &[u8]) -> Option<&BTreeSet<CidBytes>> {
+        self.ancestors.get(cid)
+    }
+
+    /// Transitive subclasses of a node, not including the node itself.
+    pub fn descendants(&self, cid: &[u8]) -> Option<&BTreeSet<CidBytes>> {
+        self.descendants.get(cid)
+    }
+
+    /// Whether `sub` is a (transitive) subclass of `sup`. Every node is a subclass of itself.
+    pub fn is_subclass_of(&self, sub: &[u8], sup: &[u8]) -> bool {
+        match self.ancestors.get(sub) {
+            Some(ancestors) => sub == sup || ancestors.contains(sup),
+            None => false,
+        }
+    }
+
+    /// Nodes without superclasses.
+    pub fn roots(&self) -> BTreeSet<CidBytes> {
+        self.parents
+            .iter()
+            .filter(|(_, parents)| parents.is_empty())
+            .map(|(cid, _)| cid.clone())
+            .collect()
+    }
+
+    /// Nodes without subclasses.
+    pub fn leaves(&self) -> BTreeSet<CidBytes> {
+        self.children
+            .iter()
+            .filter(|(_, children)| children.is_empty())
+            .map(|(cid, _)| cid.clone())
+            .collect()
+    }
+
+    /// Most specific nodes that both `a` and `b` are subclasses of.
+    ///
+    /// As the hierarchy is a DAG, there may be more than one of them. A node counts as its own
+    /// ancestor here, so the result for a node and one of its ancestors is that ancestor.
+    pub fn least_common_ancestors(&self, a: &[u8], b: &[u8]) -> BTreeSet<CidBytes> {
+        let with_self = |cid: &[u8]| -> BTreeSet<CidBytes> {
+            let mut set = self.ancestors.get(cid).cloned().unwrap_or_default();
+            if self.contains(cid) {
+                set.insert(cid.to_vec());
+            }
+            set
+        };
+        let common: BTreeSet<CidBytes> =
+            with_self(a).intersection(&with_self(b)).cloned().collect();
+
+        common
+            .iter()
+            .filter(|candidate| self.descendants[*candidate].is_disjoint(&common))
+            .cloned()
+            .collect()
+    }
+}
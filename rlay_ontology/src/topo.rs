@@ -0,0 +1,117 @@
+//! Dependency order of entity batches, e.g. for uploading them to a store that requires
+//! referenced entities to exist before their referrers.
+use crate::ontology::{CidFields, Entity};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+type CidBytes = Vec<u8>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TopoError {
+    /// The entities reference each other in a cycle, so there is no dependency order.
+    Cycle(Vec<CidBytes>),
+}
+
+impl fmt::Display for TopoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TopoError::Cycle(cids) => {
+                write!(f, "cyclic references between {} entities", cids.len())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TopoError {}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TopoOrder {
+    /// CIDs and entities of the batch, dependencies first.
+    pub entities: Vec<(CidBytes, Entity)>,
+    /// CIDs referenced from the batch without being part of it, with the CIDs of the entities
+    /// referencing them.
+    pub external: BTreeMap<CidBytes, BTreeSet<CidBytes>>,
+}
+
+impl TopoOrder {
+    pub fn cids(&self) -> impl Iterator<Item = &CidBytes> {
+        self.entities.iter().map(|(cid, _)| cid)
+    }
+}
+
+/// Orders the entities so that every entity comes after all entities of the batch it references
+/// via its CID fields. Duplicates are removed.
+///
+/// Among entities whose dependencies are all placed, the one with the smallest CID comes first,
+/// so the order only depends on the set of entities, not on the order they are provided in.
+pub fn topo_sort<'a, I: IntoIterator<Item = &'a Entity>>(
+    entities: I,
+) -> Result<TopoOrder, TopoError> {
+    topo_sort_keyed(
+        entities
+            .into_iter()
+            .map(|entity| (entity.to_bytes(), entity.clone()))
+            .collect(),
+    )
+}
+
+/// Like [`topo_sort`], for entities that are identified by the provided CIDs instead of their
+/// computed ones, e.g. entities under a different CID scheme or entities being edited.
+pub fn topo_sort_keyed(entities: BTreeMap<CidBytes, Entity>) -> Result<TopoOrder, TopoError> {
+    let mut external: BTreeMap<CidBytes, BTreeSet<CidBytes>> = BTreeMap::new();
+    let mut waiting_on: BTreeMap<CidBytes, BTreeSet<CidBytes>> = BTreeMap::new();
+    let mut dependents: BTreeMap<CidBytes, BTreeSet<CidBytes>> = BTreeMap::new();
+    for (cid, entity) in entities.iter() {
+        let mut references = BTreeSet::new();
+        for target in entity.iter_cid_fields() {
+            if target == cid {
+                continue;
+            }
+            match entities.contains_key(target) {
+                true => {
+                    references.insert(target.clone());
+                    dependents
+                        .entry(target.clone())
+                        .or_default()
+                        .insert(cid.clone());
+                }
+                false => {
+                    external
+                        .entry(target.clone())
+                        .or_default()
+                        .insert(cid.clone());
+                }
+            }
+        }
+        waiting_on.insert(cid.clone(), references);
+    }
+
+    let mut ready: BTreeSet<CidBytes> = waiting_on
+        .iter()
+        .filter(|(_, references)| references.is_empty())
+        .map(|(cid, _)| cid.clone())
+        .collect();
+    let mut entities = entities;
+    let mut ordered = Vec::new();
+    while let Some(cid) = ready.iter().next().cloned() {
+        ready.remove(&cid);
+        waiting_on.remove(&cid);
+        for dependent in dependents.remove(&cid).unwrap_or_default() {
+            let references = waiting_on.get_mut(&dependent).unwrap();
+            references.remove(&cid);
+            if references.is_empty() {
+                ready.insert(dependent);
+            }
+        }
+        let entity = entities.remove(&cid).unwrap();
+        ordered.push((cid, entity));
+    }
+
+    match waiting_on.is_empty() {
+        true => Ok(TopoOrder {
+            entities: ordered,
+            external,
+        }),
+        false => Err(TopoError::Cycle(waiting_on.keys().cloned().collect())),
+    }
+}
@@ -0,0 +1,150 @@
+//! Canonical entities for the well-known OWL, RDFS, XSD and Dublin Core vocabulary.
+//!
+//! As `AnnotationProperty`, `Class` and `Datatype` entities carry nothing but annotations, each
+//! well-known entity is made unique by a single annotation stating its IRI: an `Annotation` whose
+//! `property` is the raw bytes of [`IRI_PROPERTY`] and whose `value` is the raw bytes of the IRI.
+//! Everyone constructing the entities this way arrives at the same CIDs, which are provided as
+//! precomputed constants.
+use crate::ontology::{Annotation, AnnotationProperty, Class, Datatype, Entity};
+use cid_fork_rlay::ToCid;
+
+/// IRI used as the `property` of the annotations that identify the well-known entities.
+pub const IRI_PROPERTY: &str = "http://www.w3.org/2000/01/rdf-schema#isDefinedBy";
+
+/// The annotation that identifies the well-known entity with the IRI.
+pub fn iri_annotation(iri: &str) -> Annotation {
+    Annotation {
+        annotations: Vec::new(),
+        property: IRI_PROPERTY.as_bytes().to_vec(),
+        value: iri.as_bytes().to_vec(),
+    }
+}
+
+macro_rules! vocabulary {
+    ($($kind:ident $name:ident, $cid:ident, $iri_const:ident = $iri:literal, $bytes:expr;)*) => {
+        $(
+            #[doc = "IRI of `"]
+            #[doc = $iri]
+            #[doc = "`."]
+            pub const $iri_const: &str = $iri;
+
+            #[doc = "Precomputed CID of the canonical entity for `"]
+            #[doc = $iri]
+            #[doc = "`."]
+            pub const $cid: &[u8] = $bytes;
+
+            #[doc = "The canonical entity for `"]
+            #[doc = $iri]
+            #[doc = "`."]
+            pub fn $name() -> $kind {
+                let mut entity = $kind::default();
+                entity
+                    .annotations
+                    .push(iri_annotation($iri_const).to_cid().unwrap().to_bytes());
+                entity
+            }
+        )*
+
+        /// All well-known entities, each preceded by the annotation that identifies it.
+        pub fn entities() -> Vec<Entity> {
+            vec![
+                $(
+                    iri_annotation($iri_const).into(),
+                    $name().into(),
+                )*
+            ]
+        }
+    };
+}
+
+vocabulary! {
+    AnnotationProperty rdfs_label, RDFS_LABEL, RDFS_LABEL_IRI =
+        "http://www.w3.org/2000/01/rdf-schema#label",
+        b"\x01\x97\x80\x03\x1b\x20\x00\x20\x29\x2c\xd1\xa5\x1e\x00\x19\xb7\xf8\x37\x70\
+          \xeb\x8a\x29\xe8\x6f\x2c\x5b\x6d\x91\x74\x81\x61\x49\x3a\x84\x57\x87\xb7\xfd";
+    AnnotationProperty rdfs_comment, RDFS_COMMENT, RDFS_COMMENT_IRI =
+        "http://www.w3.org/2000/01/rdf-schema#comment",
+        b"\x01\x97\x80\x03\x1b\x20\xd2\xd2\xf0\xa6\xfe\xb9\x11\x48\x33\x30\x98\xde\x76\
+          \x9f\xb9\xef\x88\xab\x96\x2e\x42\xde\x04\xaf\xfc\xb6\x6b\x4d\x57\x12\x6e\x2e";
+    AnnotationProperty rdfs_see_also, RDFS_SEE_ALSO, RDFS_SEE_ALSO_IRI =
+        "http://www.w3.org/2000/01/rdf-schema#seeAlso",
+        b"\x01\x97\x80\x03\x1b\x20\x17\xd6\x69\x75\xc2\xcf\x64\x06\x06\xcb\x08\x70\x54\
+          \xd2\x24\x01\x78\x27\x0f\x3f\xc5\xf5\x2d\x4a\x6e\x9b\x00\x6a\x59\x76\x2d\x9f";
+    AnnotationProperty dcterms_language, DCTERMS_LANGUAGE, DCTERMS_LANGUAGE_IRI =
+        "http://purl.org/dc/terms/language",
+        b"\x01\x97\x80\x03\x1b\x20\x3b\x87\xd5\x1e\x9a\x81\xd2\xde\xd7\x03\x9e\x75\x7b\
+          \x3b\xd5\x1c\x42\x82\x63\x0f\x12\x78\x3c\xf9\xc6\x6b\x55\x2a\x0b\xae\x2a\xc5";
+    Class owl_thing, OWL_THING, OWL_THING_IRI =
+        "http://www.w3.org/2002/07/owl#Thing",
+        b"\x01\x80\x80\x03\x1b\x20\x69\xda\x24\x6b\x97\x81\x5c\x67\x61\xeb\x06\x30\xe1\
+          \xc2\xa5\x98\x2b\xd4\xdd\xe8\xf5\x6d\xff\x66\x39\xca\x8c\xc0\xfa\x4a\xd6\xd3";
+    Class owl_nothing, OWL_NOTHING, OWL_NOTHING_IRI =
+        "http://www.w3.org/2002/07/owl#Nothing",
+        b"\x01\x80\x80\x03\x1b\x20\xf8\xc1\xbd\x55\x98\x59\x5d\x51\xf9\x84\x3a\xcc\x97\
+          \xa1\xdc\xba\x7d\xce\x33\x41\x86\xf1\xc9\xea\x7b\x87\x76\x7a\x81\x8d\x41\x3b";
+    Datatype rdf_lang_string, RDF_LANG_STRING, RDF_LANG_STRING_IRI =
+        "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString",
+        b"\x01\xa1\x80\x03\x1b\x20\xc4\xdd\xc9\x96\x74\xae\x97\x53\x1f\x3c\x05\xf3\x7d\
+          \xb8\x23\xeb\x8a\x66\x18\x79\x9a\x56\x47\xa6\x20\xaf\xe9\x70\xfb\x07\xbe\x13";
+    Datatype xsd_string, XSD_STRING, XSD_STRING_IRI =
+        "http://www.w3.org/2001/XMLSchema#string",
+        b"\x01\xa1\x80\x03\x1b\x20\xe7\xa3\xbc\xa6\xf4\xc4\x30\x3b\xa7\x3d\x85\xd2\xab\
+          \xd0\x2e\x8d\x3c\x84\xc0\xf3\xbb\x9c\x30\x7f\x77\xea\xbc\x80\xe7\x1c\x36\xc6";
+    Datatype xsd_integer, XSD_INTEGER, XSD_INTEGER_IRI =
+        "http://www.w3.org/2001/XMLSchema#integer",
+        b"\x01\xa1\x80\x03\x1b\x20\x4d\x10\x57\x5d\xc1\x8a\xa6\x3a\x02\x5f\x70\x73\x71\
+          \xb2\xad\xf4\x5c\x07\x91\xa3\x07\x84\xe3\xa0\xc0\x74\x0e\xd3\x3d\xd0\x13\x8c";
+    Datatype xsd_decimal, XSD_DECIMAL, XSD_DECIMAL_IRI =
+        "http://www.w3.org/2001/XMLSchema#decimal",
+        b"\x01\xa1\x80\x03\x1b\x20\x7c\xdd\xe1\x05\x25\xbf\xb7\xff\xd5\x49\xd1\xa5\x8b\
+          \x81\x25\xfe\x7e\x0a\x3d\x5f\x21\xc2\x9c\x4e\xe2\xb7\x44\xbe\xb6\x9e\x0e\xf2";
+    Datatype xsd_boolean, XSD_BOOLEAN, XSD_BOOLEAN_IRI =
+        "http://www.w3.org/2001/XMLSchema#boolean",
+        b"\x01\xa1\x80\x03\x1b\x20\xd4\x99\x72\x67\x91\x99\xdb\xb3\x20\xdb\x61\xb2\x17\
+          \xe6\xf3\xd8\x80\x72\x2f\x54\x5e\x55\x64\x7e\x48\x62\x9a\xa3\xd7\x10\x36\xcd";
+    Datatype xsd_date_time, XSD_DATE_TIME, XSD_DATE_TIME_IRI =
+        "http://www.w3.org/2001/XMLSchema#dateTime",
+        b"\x01\xa1\x80\x03\x1b\x20\x40\xb5\x6c\xbd\x9b\xc6\x14\x99\xfe\xdd\x76\xb0\xe6\
+          \x18\x20\xbd\x7d\xe4\xdb\xa2\x1c\x9c\x7f\x44\xae\xed\xe7\x47\x1a\x66\x8a\xef";
+    Datatype xsd_base64_binary, XSD_BASE64_BINARY, XSD_BASE64_BINARY_IRI =
+        "http://www.w3.org/2001/XMLSchema#base64Binary",
+        b"\x01\xa1\x80\x03\x1b\x20\xbe\x22\x68\x5b\x2b\xd1\x42\x1a\xa8\x1f\x2a\xb3\xfa\
+          \x85\xf9\xc6\xad\x24\x3b\x1f\x14\x4c\xe9\xb6\x43\x73\x1e\x0d\x2d\xaa\xc4\x8b";
+}
+
+/// An `rdfs:label` annotation with the text as its value.
+pub fn label(text: &str) -> Annotation {
+    Annotation {
+        annotations: Vec::new(),
+        property: RDFS_LABEL.to_vec(),
+        value: text.as_bytes().to_vec(),
+    }
+}
+
+/// An `rdfs:label` annotation with the text as its value, tagged with the language by a nested
+/// [`language`](fn.language.html) annotation, which has to be stored along with it.
+pub fn label_in(text: &str, language: &str) -> Annotation {
+    let mut annotation = label(text);
+    annotation
+        .annotations
+        .push(self::language(language).to_cid().unwrap().to_bytes());
+    annotation
+}
+
+/// A `dcterms:language` annotation with the lowercased language tag as its value.
+pub fn language(tag: &str) -> Annotation {
+    Annotation {
+        annotations: Vec::new(),
+        property: DCTERMS_LANGUAGE.to_vec(),
+        value: tag.to_lowercase().into_bytes(),
+    }
+}
+
+/// An `rdfs:comment` annotation with the text as its value.
+pub fn comment(text: &str) -> Annotation {
+    Annotation {
+        annotations: Vec::new(),
+        property: RDFS_COMMENT.to_vec(),
+        value: text.as_bytes().to_vec(),
+    }
+}
@@ -0,0 +1,61 @@
+use cid_fork_rlay::ToCid;
+use rlay_ontology::prelude::*;
+
+fn label() -> Annotation {
+    Annotation::builder()
+        .property(&b"\x01\x02".to_vec())
+        .value(b"\x03".to_vec())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn builder_computes_referenced_cids() {
+    let label = label();
+    let parent = Class::default();
+
+    let klass = Class::builder()
+        .annotation(&label)
+        .super_class(&parent)
+        .build()
+        .unwrap();
+
+    let expected = Class {
+        annotations: vec![label.to_cid().unwrap().to_bytes()],
+        super_class_expression: vec![parent.to_cid().unwrap().to_bytes()],
+    };
+    assert_eq!(expected, klass);
+}
+
+#[test]
+fn builder_accepts_cids() {
+    let label = label();
+    let label_cid = label.to_cid().unwrap();
+    let label_entity: Entity = label.clone().into();
+
+    let from_entity = Class::builder().annotation(&label).build().unwrap();
+    let from_cid = Class::builder().annotation(&label_cid).build().unwrap();
+    let from_bytes = Class::builder()
+        .annotation(&label_cid.to_bytes())
+        .build()
+        .unwrap();
+    let from_wrapped = Class::builder().annotation(&label_entity).build().unwrap();
+
+    assert_eq!(from_entity, from_cid);
+    assert_eq!(from_entity, from_bytes);
+    assert_eq!(from_entity, from_wrapped);
+}
+
+#[test]
+fn builder_enforces_required_fields() {
+    let individual = Class::default();
+
+    assert_eq!(
+        Err(BuildError::MissingField("class")),
+        ClassAssertion::builder().subject(&individual).build()
+    );
+    assert_eq!(
+        Err(BuildError::MissingField("property")),
+        Annotation::builder().value(b"\x03".to_vec()).build()
+    );
+}
@@ -0,0 +1,147 @@
+use rlay_ontology::ontology::canonical::CanonicalError;
+use rlay_ontology::prelude::*;
+
+fn annotation() -> Annotation {
+    Annotation {
+        property: b"\x01\x02".to_vec(),
+        value: b"\x03".to_vec(),
+        ..Annotation::default()
+    }
+}
+
+#[test]
+fn strict_accepts_serializer_output() {
+    let entity = EntityV0::Annotation(annotation());
+    let mut serialized = Vec::new();
+    entity.serialize(&mut serialized).unwrap();
+
+    assert_eq!(Ok(entity), EntityV0::deserialize_strict(&serialized));
+}
+
+#[test]
+fn strict_compact_accepts_serializer_output() {
+    let bytes = b"\xa2\x68property\x42\x01\x02\x65value\x41\x03";
+
+    let decoded = AnnotationFormatCompact::from_slice_strict(bytes).unwrap();
+    assert_eq!(annotation(), Annotation::from_compact_format(decoded));
+}
+
+#[test]
+fn strict_rejects_unsorted_keys() {
+    let bytes = b"\xa2\x65value\x41\x03\x68property\x42\x01\x02";
+
+    match AnnotationFormatCompact::from_slice_strict(bytes) {
+        Err(CanonicalError::UnsortedKeys { key, .. }) => assert_eq!("property", key),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn strict_rejects_duplicate_keys() {
+    let bytes = b"\xa3\x68property\x42\x01\x02\x68property\x42\x01\x02\x65value\x41\x03";
+
+    match AnnotationFormatCompact::from_slice_strict(bytes) {
+        Err(CanonicalError::DuplicateKey { key, .. }) => assert_eq!("property", key),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn strict_rejects_non_minimal_integers() {
+    // length of "property" value encoded with an additional length byte
+    let bytes = b"\xa2\x68property\x58\x02\x01\x02\x65value\x41\x03";
+
+    assert_eq!(
+        Err(CanonicalError::NonMinimalInteger { offset: 10 }),
+        AnnotationFormatCompact::from_slice_strict(bytes)
+    );
+}
+
+#[test]
+fn strict_rejects_indefinite_length() {
+    let bytes = b"\xa1\x6bannotations\x9f\x83\x01\x02\x03\xff";
+
+    assert_eq!(
+        Err(CanonicalError::IndefiniteLength { offset: 13 }),
+        ClassFormatCompact::from_slice_strict(bytes)
+    );
+    assert!(
+        ClassFormatCompact::from_slice_strict(b"\xa1\x6bannotations\x81\x83\x01\x02\x03").is_ok()
+    );
+}
+
+#[test]
+fn strict_rejects_explicit_null() {
+    let bytes = b"\xa2\x67subject\xf6\x65class\x41\x03";
+
+    match ClassAssertionFormatCompact::from_slice_strict(bytes) {
+        Err(CanonicalError::ExplicitNull { key, .. }) => assert_eq!("subject", key),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn strict_rejects_empty_array_field() {
+    let bytes = b"\xa3\x6bannotations\x80\x68property\x42\x01\x02\x65value\x41\x03";
+
+    match AnnotationFormatCompact::from_slice_strict(bytes) {
+        Err(CanonicalError::EmptyArray { key, .. }) => assert_eq!("annotations", key),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn strict_accepts_empty_optional_bytes() {
+    let entity = EntityV0::Literal(Literal {
+        datatype: Some(vec![]),
+        ..Literal::default()
+    });
+    let mut serialized = Vec::new();
+    entity.serialize(&mut serialized).unwrap();
+
+    assert_eq!(Ok(entity), EntityV0::deserialize_strict(&serialized));
+}
+
+#[test]
+fn strict_rejects_packed_keys() {
+    let bytes = b"\xa2\x01\x42\x01\x02\x02\x41\x03";
+
+    assert_eq!(
+        Err(CanonicalError::NonStringKey { offset: 1 }),
+        AnnotationFormatCompact::from_slice_strict(bytes)
+    );
+}
+
+#[test]
+fn strict_rejects_trailing_bytes() {
+    let bytes = b"\xa2\x68property\x42\x01\x02\x65value\x41\x03\x00";
+
+    assert_eq!(
+        Err(CanonicalError::TrailingBytes { offset: 21 }),
+        AnnotationFormatCompact::from_slice_strict(bytes)
+    );
+}
+
+#[test]
+fn strict_v0_rejects_non_minimal_varint() {
+    let mut serialized = Vec::new();
+    EntityV0::Annotation(annotation())
+        .serialize(&mut serialized)
+        .unwrap();
+    // version 0 encoded as a two byte varint
+    let mut padded = vec![0x80, 0x00];
+    padded.extend_from_slice(&serialized[1..]);
+
+    assert_eq!(
+        Err(CanonicalError::NonMinimalVarint),
+        EntityV0::deserialize_strict(&padded)
+    );
+}
+
+#[test]
+fn strict_v0_rejects_unknown_kind() {
+    assert_eq!(
+        Err(CanonicalError::UnknownKind(99)),
+        EntityV0::deserialize_strict(b"\x00\x63\xa0")
+    );
+}
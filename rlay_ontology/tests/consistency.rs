@@ -0,0 +1,128 @@
+use rlay_ontology::consistency::{Conflict, ConsistencyChecker};
+use rlay_ontology::prelude::*;
+
+fn cid<E: Into<Entity>>(entity: E) -> Vec<u8> {
+    entity.into().to_bytes()
+}
+
+#[test]
+fn consistency_finds_complementary_assertions() {
+    let class = Class::default();
+    let (alice, bob) = (b"alice".to_vec(), b"bob".to_vec());
+
+    let positive = ClassAssertion::builder()
+        .subject(&alice)
+        .class(&class)
+        .build()
+        .unwrap();
+    let negative = NegativeClassAssertion::builder()
+        .annotation(&b"note".to_vec())
+        .subject(&alice)
+        .class(&class)
+        .build()
+        .unwrap();
+    let unrelated = NegativeClassAssertion::builder()
+        .subject(&bob)
+        .class(&class)
+        .build()
+        .unwrap();
+
+    let entities: Vec<Entity> = vec![
+        positive.clone().into(),
+        negative.clone().into(),
+        unrelated.into(),
+        class.into(),
+    ];
+    let checker = ConsistencyChecker::from_entities(&entities);
+
+    assert_eq!(
+        vec![Conflict {
+            family: EntityKind::ClassAssertion,
+            positive: cid(positive),
+            negative: cid(negative),
+            inferred: false,
+        }],
+        checker.conflicts()
+    );
+}
+
+#[test]
+fn consistency_covers_property_assertions() {
+    let property = ObjectProperty::default();
+    let (alice, bob) = (b"alice".to_vec(), b"bob".to_vec());
+
+    let positive = ObjectPropertyAssertion::builder()
+        .subject(&alice)
+        .property(&property)
+        .target(&bob)
+        .build()
+        .unwrap();
+    let negative = NegativeObjectPropertyAssertion::builder()
+        .subject(&alice)
+        .property(&property)
+        .target(&bob)
+        .build()
+        .unwrap();
+    let data_positive = DataPropertyAssertion::builder()
+        .subject(&alice)
+        .property(&property)
+        .target(b"42".to_vec())
+        .build()
+        .unwrap();
+    let data_negative = NegativeDataPropertyAssertion::builder()
+        .subject(&alice)
+        .property(&property)
+        .target(b"43".to_vec())
+        .build()
+        .unwrap();
+
+    let entities: Vec<Entity> = vec![
+        positive.into(),
+        negative.into(),
+        data_positive.into(),
+        data_negative.into(),
+    ];
+    let conflicts = ConsistencyChecker::from_entities(&entities).conflicts();
+
+    assert_eq!(1, conflicts.len());
+    assert_eq!(EntityKind::ObjectPropertyAssertion, conflicts[0].family);
+}
+
+#[cfg(feature = "reasoner")]
+#[test]
+fn consistency_finds_conflicts_with_inferred_types() {
+    use rlay_ontology::reasoner::Reasoner;
+
+    let agent = Class::default();
+    let org = Class::builder().super_class(&agent).build().unwrap();
+    let acme = b"acme".to_vec();
+
+    let entities: Vec<Entity> = vec![
+        agent.clone().into(),
+        org.clone().into(),
+        ClassAssertion::builder()
+            .subject(&acme)
+            .class(&org)
+            .build()
+            .unwrap()
+            .into(),
+        NegativeClassAssertion::builder()
+            .subject(&acme)
+            .class(&agent)
+            .build()
+            .unwrap()
+            .into(),
+    ];
+    let checker = ConsistencyChecker::from_entities(&entities);
+    let realization = Reasoner::from_entities(&entities).realize();
+
+    assert!(checker.conflicts().is_empty());
+    let conflicts = checker.conflicts_with_realization(&realization);
+    assert_eq!(1, conflicts.len());
+    assert!(conflicts[0].inferred);
+    assert_eq!(cid(entities[3].clone()), conflicts[0].negative);
+    assert_eq!(
+        realization.inferred_class_assertions()[0].cid,
+        conflicts[0].positive
+    );
+}
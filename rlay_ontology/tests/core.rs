@@ -29,3 +29,167 @@ fn call_with_entity_kinds() {
 
     rlay_ontology::call_with_entity_kinds!(ALL; test_field_names!);
 }
+
+#[test]
+fn kind_schema() {
+    let schema = EntityKind::ClassAssertion.schema();
+
+    assert_eq!("ClassAssertion", schema.name);
+    assert_eq!(24, schema.kind_id);
+    assert_eq!(None, schema.expression_kind);
+    let field_names: Vec<_> = schema.fields.iter().map(|n| n.name).collect();
+    assert_eq!(vec!["annotations", "subject", "class"], field_names);
+
+    let class_field = schema.field("class").unwrap();
+    assert!(class_field.required);
+    assert!(!class_field.array);
+    assert_eq!("IRI", class_field.kind);
+}
+
+#[test]
+fn kind_schemas_match_kinds() {
+    for (i, schema) in EntityKind::schemas().iter().enumerate() {
+        let kind = EntityKind::from_name(schema.name).unwrap();
+        assert_eq!(kind.id(), schema.kind_id);
+        assert_eq!(i as u64, schema.kind_id);
+    }
+
+    let super_class = EntityKind::Class
+        .schema()
+        .field("superClassExpression")
+        .unwrap();
+    assert_eq!("ClassExpression", super_class.element_kind());
+    assert_eq!(
+        Some("ClassExpression"),
+        EntityKind::Class.schema().expression_kind
+    );
+    assert!(EntityKind::Annotation.schema().field("value").unwrap().data);
+}
+
+#[test]
+fn entity_get_field() {
+    let entity: Entity = ClassAssertion {
+        subject: Some(b"\x01".to_vec()),
+        class: b"\x02".to_vec(),
+        ..ClassAssertion::default()
+    }
+    .into();
+
+    assert_eq!(
+        Some(FieldValue::Array(vec![])),
+        entity.get_field("annotations")
+    );
+    assert_eq!(
+        Some(FieldValue::OptionalBytes(Some(b"\x01".to_vec()))),
+        entity.get_field("subject")
+    );
+    assert_eq!(
+        Some(FieldValue::Bytes(b"\x02".to_vec())),
+        entity.get_field("class")
+    );
+    assert_eq!(None, entity.get_field("superClassExpression"));
+}
+
+#[test]
+fn entity_set_field() {
+    let mut entity = EntityKind::Class.empty_entity();
+
+    entity
+        .set_field(
+            "superClassExpression",
+            FieldValue::Array(vec![b"\x01".to_vec()]),
+        )
+        .unwrap();
+    let expected: Entity = Class {
+        super_class_expression: vec![b"\x01".to_vec()],
+        ..Class::default()
+    }
+    .into();
+    assert_eq!(expected, entity);
+
+    assert_eq!(
+        Err(FieldError::WrongShape {
+            field: "annotations"
+        }),
+        entity.set_field("annotations", FieldValue::Bytes(vec![]))
+    );
+    assert_eq!(
+        Err(FieldError::UnknownField("class".to_owned())),
+        entity.set_field("class", FieldValue::Bytes(vec![]))
+    );
+}
+
+#[test]
+fn assertion_complement() {
+    let assertion = DataPropertyAssertion {
+        annotations: vec![b"note".to_vec()],
+        subject: Some(b"subject".to_vec()),
+        property: Some(b"property".to_vec()),
+        target: Some(b"target".to_vec()),
+    };
+    let complement = NegativeDataPropertyAssertion {
+        annotations: vec![],
+        subject: Some(b"subject".to_vec()),
+        property: Some(b"property".to_vec()),
+        target: Some(b"target".to_vec()),
+    };
+
+    assert_eq!(complement, assertion.get_assertion_complement());
+    let entity: Entity = assertion.into();
+    let complement_entity: Entity = complement.clone().into();
+    assert_eq!(
+        Some(complement_entity.clone()),
+        entity.assertion_complement()
+    );
+    assert_eq!(
+        complement,
+        complement
+            .get_assertion_complement()
+            .get_assertion_complement()
+    );
+}
+
+#[test]
+fn entity_polarity() {
+    let positive: Entity = AnnotationAssertion::default().into();
+    let negative: Entity = NegativeObjectPropertyAssertion::default().into();
+    let class: Entity = Class::default().into();
+
+    assert_eq!(Polarity::Positive, positive.polarity());
+    assert_eq!(Polarity::Negative, negative.polarity());
+    assert_eq!(Polarity::NotAnAssertion, class.polarity());
+    assert_eq!(None, class.assertion_complement());
+}
+
+#[test]
+fn entity_as_assertion() {
+    let class_assertion: Entity = ClassAssertion {
+        annotations: vec![],
+        subject: Some(b"subject".to_vec()),
+        class: b"class".to_vec(),
+    }
+    .into();
+    let annotation_assertion: Entity = NegativeAnnotationAssertion {
+        annotations: vec![],
+        subject: Some(b"subject".to_vec()),
+        property: Some(b"property".to_vec()),
+        value: Some(b"value".to_vec()),
+    }
+    .into();
+
+    let assertion = class_assertion.as_assertion().unwrap();
+    assert_eq!(Some(&b"subject".to_vec()), assertion.subject());
+    assert_eq!(Some(&b"class".to_vec()), assertion.predicate());
+    assert_eq!(None, assertion.object());
+
+    let assertion = annotation_assertion.as_assertion().unwrap();
+    assert_eq!(Some(&b"property".to_vec()), assertion.predicate());
+    assert_eq!(Some(&b"value".to_vec()), assertion.object());
+    assert_eq!(
+        Some(&b"subject".to_vec()),
+        annotation_assertion.get_subject()
+    );
+
+    let class: Entity = Class::default().into();
+    assert!(class.as_assertion().is_none());
+}
@@ -0,0 +1,104 @@
+#[macro_use]
+extern crate serde_json;
+
+use rlay_ontology::diff::diff;
+use rlay_ontology::prelude::*;
+use rlay_ontology::vocab;
+
+fn class(label: &Annotation, parents: &[&Class]) -> Class {
+    let mut builder = Class::builder().annotation(label);
+    for parent in parents {
+        builder = builder.super_class(*parent);
+    }
+    builder.build().unwrap()
+}
+
+fn age(subject: &[u8], value: &str) -> DataPropertyAssertion {
+    DataPropertyAssertion::builder()
+        .subject(subject)
+        .property(&DataProperty::default())
+        .target(value)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn diff_identical() {
+    let entities: Vec<Entity> = vec![vocab::label("Agent").into()];
+    assert!(diff(&entities, &entities).is_empty());
+    assert_eq!("", diff(&entities, &entities).to_string());
+}
+
+#[test]
+fn diff_added_removed_modified() {
+    let agent_label = vocab::label("Agent");
+    let org_label = vocab::label("Organization");
+    let agent = class(&agent_label, &[]);
+    let org = class(&org_label, &[]);
+    let org_v2 = class(&org_label, &[&agent]);
+    let thing = vocab::owl_thing();
+
+    let old: Vec<Entity> = vec![
+        agent_label.clone().into(),
+        org_label.clone().into(),
+        agent.clone().into(),
+        org.clone().into(),
+        thing.clone().into(),
+        age(b"alice", "33").into(),
+    ];
+    let new: Vec<Entity> = vec![
+        agent_label.into(),
+        org_label.into(),
+        agent.into(),
+        org_v2.clone().into(),
+        age(b"alice", "34").into(),
+        age(b"bob", "40").into(),
+    ];
+
+    let result = diff(&old, &new);
+    assert_eq!(2, result.kinds.len());
+
+    let classes = result.kind("Class").unwrap();
+    assert!(classes.added.is_empty());
+    assert_eq!(vec![thing.cid_bytes()], classes.removed);
+    assert_eq!(1, classes.modified.len());
+    assert_eq!(org.cid_bytes(), classes.modified[0].old);
+    assert_eq!(org_v2.cid_bytes(), classes.modified[0].new);
+    assert_eq!(
+        vec!["superClassExpression"],
+        classes.modified[0].changed_fields
+    );
+
+    let assertions = result.kind("DataPropertyAssertion").unwrap();
+    assert_eq!(vec![age(b"bob", "40").cid_bytes()], assertions.added);
+    assert!(assertions.removed.is_empty());
+    assert_eq!(age(b"alice", "33").cid_bytes(), assertions.modified[0].old);
+    assert_eq!(vec!["target"], assertions.modified[0].changed_fields);
+
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(
+        json!(["target"]),
+        json["kinds"]["DataPropertyAssertion"]["modified"][0]["changed_fields"]
+    );
+    assert!(json["kinds"]["Class"]["removed"][0].is_string());
+
+    let text = result.to_string();
+    assert!(text.starts_with("Class:\n  - "));
+    assert!(text.contains("(superClassExpression)"));
+}
+
+#[test]
+fn diff_ambiguous_pairing() {
+    let label = vocab::label("Agent");
+    let old: Vec<Entity> = vec![label.clone().into(), class(&label, &[]).into()];
+    let new: Vec<Entity> = vec![
+        label.clone().into(),
+        class(&label, &[&vocab::owl_thing()]).into(),
+        class(&label, &[&vocab::owl_nothing()]).into(),
+    ];
+
+    let classes = diff(&old, &new).kind("Class").cloned().unwrap();
+    assert_eq!(2, classes.added.len());
+    assert_eq!(1, classes.removed.len());
+    assert!(classes.modified.is_empty());
+}
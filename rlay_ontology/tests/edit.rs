@@ -0,0 +1,120 @@
+use rlay_ontology::edit::{apply_edits, Edit, EditError};
+use rlay_ontology::prelude::*;
+use rlay_ontology::vocab;
+use std::collections::BTreeMap;
+
+fn store(entities: Vec<Entity>) -> BTreeMap<Vec<u8>, Entity> {
+    entities
+        .into_iter()
+        .map(|entity| (entity.to_bytes(), entity))
+        .collect()
+}
+
+#[test]
+fn edit_cascades_to_dependents() {
+    let label = vocab::label("Organisation");
+    let agent = Class::default();
+    let org = Class::builder()
+        .annotation(&label)
+        .super_class(&agent)
+        .build()
+        .unwrap();
+    let company = Class::builder().super_class(&org).build().unwrap();
+    let mut store = store(vec![
+        label.clone().into(),
+        agent.clone().into(),
+        org.clone().into(),
+        company.clone().into(),
+    ]);
+
+    let edit =
+        Edit::new(&label.cid_bytes()).set("value", FieldValue::Bytes(b"Organization".to_vec()));
+    let mapping = apply_edits(&mut store, &[edit]).unwrap();
+
+    let new_label = vocab::label("Organization");
+    let new_org = Class::builder()
+        .annotation(&new_label)
+        .super_class(&agent)
+        .build()
+        .unwrap();
+    let new_company = Class::builder().super_class(&new_org).build().unwrap();
+
+    let mut expected = BTreeMap::new();
+    expected.insert(label.cid_bytes(), new_label.cid_bytes());
+    expected.insert(org.cid_bytes(), new_org.cid_bytes());
+    expected.insert(company.cid_bytes(), new_company.cid_bytes());
+    assert_eq!(expected, mapping);
+
+    let mut expected_cids = vec![
+        new_label.cid_bytes(),
+        agent.cid_bytes(),
+        new_org.cid_bytes(),
+        new_company.cid_bytes(),
+    ];
+    expected_cids.sort();
+    assert_eq!(expected_cids, store.keys().cloned().collect::<Vec<_>>());
+}
+
+#[test]
+fn edit_is_atomic() {
+    let label = vocab::label("Agent");
+    let class = Class::builder().annotation(&label).build().unwrap();
+    let mut store = store(vec![label.clone().into(), class.clone().into()]);
+    let before = store.clone();
+
+    let edits = vec![
+        Edit::new(&label.cid_bytes()).set("value", FieldValue::Bytes(b"Actor".to_vec())),
+        Edit::new(&class.cid_bytes()).set("unknown", FieldValue::Array(vec![])),
+    ];
+    assert_eq!(
+        Err(EditError::Field {
+            cid: class.cid_bytes(),
+            error: FieldError::UnknownField("unknown".to_owned()),
+        }),
+        apply_edits(&mut store, &edits)
+    );
+    assert_eq!(before, store);
+
+    let missing = Edit::new(b"missing").set("value", FieldValue::Bytes(vec![]));
+    assert_eq!(
+        Err(EditError::NotFound(b"missing".to_vec())),
+        apply_edits(&mut store, &[missing])
+    );
+    assert_eq!(before, store);
+}
+
+#[test]
+fn edit_swaps_values() {
+    let agent = vocab::label("Agent");
+    let org = vocab::label("Organization");
+    let mut store = store(vec![agent.clone().into(), org.clone().into()]);
+    let before = store.clone();
+
+    let edits = vec![
+        Edit::new(&agent.cid_bytes()).set("value", FieldValue::Bytes(b"Organization".to_vec())),
+        Edit::new(&org.cid_bytes()).set("value", FieldValue::Bytes(b"Agent".to_vec())),
+    ];
+    let mapping = apply_edits(&mut store, &edits).unwrap();
+
+    let mut expected = BTreeMap::new();
+    expected.insert(agent.cid_bytes(), org.cid_bytes());
+    expected.insert(org.cid_bytes(), agent.cid_bytes());
+    assert_eq!(expected, mapping);
+    assert_eq!(before, store);
+}
+
+#[test]
+fn edit_rejects_cycles() {
+    let agent = Class::default();
+    let org = Class::builder().super_class(&agent).build().unwrap();
+    let mut store = store(vec![agent.clone().into(), org.clone().into()]);
+
+    let edit = Edit::new(&agent.cid_bytes()).set(
+        "superClassExpression",
+        FieldValue::Array(vec![org.cid_bytes()]),
+    );
+    match apply_edits(&mut store, &[edit]) {
+        Err(EditError::Cycle(cids)) => assert_eq!(2, cids.len()),
+        other => panic!("expected a cycle, got {:?}", other),
+    }
+}
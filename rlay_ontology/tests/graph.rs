@@ -0,0 +1,75 @@
+use rlay_ontology::ontology::graph::GraphError;
+use rlay_ontology::ontology::{Violation, ViolationKind};
+use rlay_ontology::prelude::*;
+use rlay_ontology::rlay_graph;
+
+#[test]
+fn graph_resolves_local_references() {
+    let rdfs_label = AnnotationProperty::default();
+
+    let graph = rlay_graph! {
+        org = Class { annotations: [label], super_class_expression: [thing] };
+        label = Annotation { property: rdfs_label, value: "Organization" };
+        thing = Class {};
+    }
+    .unwrap();
+
+    let label = Annotation::builder()
+        .property(&rdfs_label)
+        .value("Organization")
+        .build()
+        .unwrap();
+    let org = Class::builder()
+        .annotation(&label)
+        .super_class(&Class::default())
+        .build()
+        .unwrap();
+
+    let label: Entity = label.into();
+    let org: Entity = org.into();
+
+    assert_eq!(3, graph.len());
+    assert_eq!(label, graph["label"].entity);
+    assert_eq!(org, graph["org"].entity);
+    assert_eq!(org.to_bytes(), graph["org"].cid);
+}
+
+#[test]
+fn graph_rejects_cycles() {
+    let result = rlay_graph! {
+        a = Class { super_class_expression: [b] };
+        b = Class { super_class_expression: [a] };
+        c = Class {};
+    };
+
+    assert_eq!(Err(GraphError::Cycle(vec!["a", "b"])), result);
+}
+
+#[test]
+fn graph_rejects_arrays_for_single_fields() {
+    let result = rlay_graph! {
+        thing = Class {};
+        assertion = ClassAssertion { subject: [thing], class: thing };
+    };
+
+    assert_eq!(Err(GraphError::WrongShape { field: "subject" }), result);
+}
+
+#[test]
+fn graph_rejects_missing_required_fields() {
+    let result = rlay_graph! {
+        thing = Class {};
+        assertion = ClassAssertion { subject: thing };
+    };
+
+    assert_eq!(
+        Err(GraphError::Invalid {
+            name: "assertion",
+            violations: vec![Violation {
+                field: "class",
+                kind: ViolationKind::Empty,
+            }],
+        }),
+        result
+    );
+}
@@ -0,0 +1,136 @@
+use rlay_ontology::index::{AssertionIndex, AssertionPattern, Backlink, BacklinkIndex};
+use rlay_ontology::prelude::*;
+use std::collections::BTreeSet;
+
+fn cid<E: Into<Entity>>(entity: E) -> Vec<u8> {
+    entity.into().to_bytes()
+}
+
+fn property_assertion(subject: &[u8], property: &[u8], target: &[u8]) -> Entity {
+    ObjectPropertyAssertion::builder()
+        .subject(subject)
+        .property(property)
+        .target(target)
+        .build()
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn assertion_index_pattern_combinations() {
+    let (alice, bob, carol) = (&b"alice"[..], &b"bob"[..], &b"carol"[..]);
+    let (knows, likes) = (&b"knows"[..], &b"likes"[..]);
+
+    let entities = vec![
+        property_assertion(alice, knows, bob),
+        property_assertion(alice, likes, bob),
+        property_assertion(alice, knows, carol),
+        property_assertion(bob, knows, carol),
+        Class::default().into(),
+    ];
+    let index = AssertionIndex::from_entities(&entities);
+    assert_eq!(4, index.len());
+
+    let sorted = |entities: Vec<&Entity>| -> Vec<Vec<u8>> {
+        let set: BTreeSet<Vec<u8>> = entities.into_iter().map(|e| e.to_bytes()).collect();
+        set.into_iter().collect()
+    };
+
+    assert_eq!(
+        sorted(vec![&entities[0], &entities[1], &entities[2]]),
+        index.query(&AssertionPattern::new().subject(alice))
+    );
+    assert_eq!(
+        sorted(vec![&entities[0], &entities[2]]),
+        index.query(&AssertionPattern::new().subject(alice).property(knows))
+    );
+    assert_eq!(
+        sorted(vec![&entities[2], &entities[3]]),
+        index.query(&AssertionPattern::new().property(knows).object(carol))
+    );
+    assert!(index
+        .query(&AssertionPattern::new().subject(carol))
+        .is_empty());
+    assert_eq!(4, index.query(&AssertionPattern::new()).len());
+}
+
+#[test]
+fn assertion_index_instances_and_incremental_updates() {
+    let class = Class::default();
+    let positive = ClassAssertion::builder()
+        .subject(&b"alice".to_vec())
+        .class(&class)
+        .build()
+        .unwrap();
+    let negative = NegativeClassAssertion::builder()
+        .subject(&b"bob".to_vec())
+        .class(&class)
+        .build()
+        .unwrap();
+    let class_cid = cid(class);
+
+    let mut index = AssertionIndex::new();
+    index.insert_entity(&positive.clone().into());
+    index.insert_entity(&negative.clone().into());
+
+    let expected: BTreeSet<Vec<u8>> = vec![b"alice".to_vec()].into_iter().collect();
+    assert_eq!(expected, index.instances_of(&class_cid));
+    assert_eq!(
+        vec![cid(negative)],
+        index.query(
+            &AssertionPattern::new()
+                .class(&class_cid)
+                .polarity(Polarity::Negative)
+        )
+    );
+
+    assert!(index.remove(&cid(positive)));
+    assert!(index.instances_of(&class_cid).is_empty());
+    assert_eq!(1, index.len());
+}
+
+#[test]
+fn backlink_index_point_and_transitive_queries() {
+    let label = Annotation::builder()
+        .property(&b"label".to_vec())
+        .value("Agent")
+        .build()
+        .unwrap();
+    let agent = Class::builder().annotation(&label).build().unwrap();
+    let org = Class::builder().super_class(&agent).build().unwrap();
+    let assertion = ClassAssertion::builder()
+        .subject(&b"acme".to_vec())
+        .class(&org)
+        .build()
+        .unwrap();
+
+    let entities: Vec<Entity> = vec![
+        label.clone().into(),
+        agent.clone().into(),
+        org.clone().into(),
+        assertion.clone().into(),
+    ];
+    let mut index = BacklinkIndex::from_entities(&entities);
+
+    assert_eq!(
+        vec![Backlink {
+            source: cid(agent.clone()),
+            field: "annotations",
+        }],
+        index.backlinks(&cid(label.clone()))
+    );
+    assert_eq!(
+        vec![Backlink {
+            source: cid(assertion.clone()),
+            field: "class",
+        }],
+        index.backlinks(&cid(org.clone()))
+    );
+    let expected: BTreeSet<Vec<u8>> = vec![cid(agent), cid(org.clone()), cid(assertion.clone())]
+        .into_iter()
+        .collect();
+    assert_eq!(expected, index.dependents(&cid(label)));
+
+    assert!(index.remove(&cid(assertion)));
+    assert!(index.backlinks(&cid(org)).is_empty());
+}
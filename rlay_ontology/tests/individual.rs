@@ -0,0 +1,134 @@
+use rlay_ontology::literal::LiteralValue;
+use rlay_ontology::ontology::individual::IndividualError;
+use rlay_ontology::prelude::*;
+use std::collections::BTreeMap;
+
+fn class_assertion(subject: &[u8], class: &Class) -> Entity {
+    ClassAssertion::builder()
+        .subject(subject)
+        .class(class)
+        .build()
+        .unwrap()
+        .into()
+}
+
+fn data_assertion(subject: &[u8], value: &str) -> Entity {
+    DataPropertyAssertion::builder()
+        .subject(subject)
+        .property(&DataProperty::default())
+        .target(LiteralValue::Integer(value.to_owned()).to_data().unwrap())
+        .build()
+        .unwrap()
+        .into()
+}
+
+fn resolver(entities: &[Entity]) -> BTreeMap<Vec<u8>, Entity> {
+    entities
+        .iter()
+        .map(|entity| (entity.to_bytes(), entity.clone()))
+        .collect()
+}
+
+#[test]
+fn individual_from_assertions_roundtrip() {
+    let entities = vec![
+        class_assertion(b"alice", &Class::default()),
+        data_assertion(b"alice", "34"),
+    ];
+
+    let individual = Individual::from_assertions(&entities).unwrap();
+    assert_eq!(vec![entities[0].to_bytes()], individual.class_assertions);
+    assert_eq!(
+        vec![entities[1].to_bytes()],
+        individual.data_property_assertions
+    );
+
+    let assertions = individual.assertions(&resolver(&entities)).unwrap();
+    assert_eq!(entities, assertions.into_entities());
+}
+
+#[test]
+fn individual_from_assertions_errors() {
+    let mixed = vec![
+        class_assertion(b"alice", &Class::default()),
+        class_assertion(b"bob", &Class::default()),
+    ];
+    assert_eq!(
+        Err(IndividualError::SubjectMismatch {
+            expected: b"alice".to_vec(),
+            found: b"bob".to_vec(),
+        }),
+        Individual::from_assertions(&mixed)
+    );
+
+    let unsupported: Vec<Entity> = vec![AnnotationAssertion::default().into()];
+    assert_eq!(
+        Err(IndividualError::UnsupportedKind(
+            EntityKind::AnnotationAssertion
+        )),
+        Individual::from_assertions(&unsupported)
+    );
+
+    let class = class_assertion(b"alice", &Class::default());
+    let individual = Individual {
+        data_property_assertions: vec![class.to_bytes()],
+        ..Individual::default()
+    };
+    assert_eq!(
+        Err(IndividualError::Unresolved(class.to_bytes())),
+        individual.assertions(&BTreeMap::new())
+    );
+    assert_eq!(
+        Err(IndividualError::WrongKind {
+            cid: class.to_bytes(),
+            expected: EntityKind::DataPropertyAssertion,
+            found: EntityKind::ClassAssertion,
+        }),
+        individual.assertions(&resolver(&[class]))
+    );
+}
+
+#[test]
+fn individual_merge() {
+    let first = vec![
+        class_assertion(b"alice", &Class::default()),
+        data_assertion(b"alice", "34"),
+    ];
+    let second = vec![
+        data_assertion(b"alice", "34"),
+        data_assertion(b"alice", "35"),
+    ];
+    let all = vec![first[0].clone(), first[1].clone(), second[1].clone()];
+
+    let merged = Individual::from_assertions(&first)
+        .unwrap()
+        .merge(
+            &Individual::from_assertions(&second).unwrap(),
+            &resolver(&all),
+        )
+        .unwrap();
+    assert_eq!(Individual::from_assertions(&all).unwrap(), merged);
+}
+
+#[test]
+fn individual_merge_errors() {
+    let alice = vec![class_assertion(b"alice", &Class::default())];
+    let bob = vec![data_assertion(b"bob", "35")];
+    let all: Vec<Entity> = alice.iter().chain(bob.iter()).cloned().collect();
+    let alice = Individual::from_assertions(&alice).unwrap();
+    let bob = Individual::from_assertions(&bob).unwrap();
+
+    assert_eq!(
+        Err(IndividualError::SubjectMismatch {
+            expected: b"alice".to_vec(),
+            found: b"bob".to_vec(),
+        }),
+        alice.merge(&bob, &resolver(&all))
+    );
+    assert_eq!(
+        Err(IndividualError::Unresolved(
+            bob.data_property_assertions[0].clone()
+        )),
+        alice.merge(&bob, &resolver(&all[..1]))
+    );
+}
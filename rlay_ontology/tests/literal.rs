@@ -0,0 +1,256 @@
+use prost::Message;
+use rlay_ontology::label::{self, Label};
+use rlay_ontology::literal::{LiteralError, LiteralValue};
+use rlay_ontology::prelude::*;
+use rlay_ontology::vocab;
+use std::collections::BTreeMap;
+
+fn literal(datatype: &[u8], value: &str) -> Literal {
+    Literal {
+        datatype: Some(datatype.to_vec()),
+        value: value.as_bytes().to_vec(),
+        language: None,
+    }
+}
+
+#[test]
+fn literal_value_roundtrip() {
+    let values = vec![
+        LiteralValue::String("Organization".to_owned()),
+        LiteralValue::LangString {
+            text: "Organisation".to_owned(),
+            lang: "en-gb".to_owned(),
+        },
+        LiteralValue::Integer("-42".to_owned()),
+        LiteralValue::Integer("123456789012345678901234567890".to_owned()),
+        LiteralValue::Decimal("3.0".to_owned()),
+        LiteralValue::Decimal("-0.25".to_owned()),
+        LiteralValue::Boolean(true),
+        LiteralValue::DateTime("2019-04-01T12:00:00.5+02:00".to_owned()),
+        LiteralValue::Bytes(vec![0, 159, 146, 150]),
+    ];
+    for value in values {
+        let literal = value.to_literal().unwrap();
+        assert_eq!(Ok(value.clone()), LiteralValue::from_literal(&literal));
+    }
+}
+
+#[test]
+fn literal_value_encoding() {
+    assert_eq!(
+        literal(vocab::XSD_INTEGER, "-42"),
+        LiteralValue::Integer("-42".to_owned())
+            .to_literal()
+            .unwrap()
+    );
+    assert_eq!(
+        Literal {
+            language: Some(b"de".to_vec()),
+            ..literal(vocab::RDF_LANG_STRING, "Agent")
+        },
+        LiteralValue::LangString {
+            text: "Agent".to_owned(),
+            lang: "de".to_owned(),
+        }
+        .to_literal()
+        .unwrap()
+    );
+    assert_eq!(
+        Err(LiteralError::MissingDatatype),
+        LiteralValue::from_literal(&Literal {
+            datatype: None,
+            value: b"plain".to_vec(),
+            language: None,
+        })
+    );
+}
+
+#[test]
+fn literal_value_mismatch() {
+    let non_canonical = vec![
+        (vocab::XSD_INTEGER, "042"),
+        (vocab::XSD_INTEGER, "-0"),
+        (vocab::XSD_INTEGER, "4.2"),
+        (vocab::XSD_DECIMAL, "1.50"),
+        (vocab::XSD_DECIMAL, "1"),
+        (vocab::XSD_DECIMAL, "-0.0"),
+        (vocab::XSD_DECIMAL, "--1.5"),
+        (vocab::XSD_BOOLEAN, "1"),
+        (vocab::XSD_DATE_TIME, "2019-04-01"),
+        (vocab::XSD_DATE_TIME, "2019-13-01T12:00:00Z"),
+        (vocab::XSD_DATE_TIME, "2019-04-01T12:00:00+00:00"),
+    ];
+    for (datatype, value) in non_canonical {
+        match LiteralValue::from_literal(&literal(datatype, value)) {
+            Err(LiteralError::InvalidValue { .. }) => {}
+            other => panic!("{:?} decoded to {:?}", value, other),
+        }
+    }
+
+    let with_language = |datatype: &[u8], language: Option<&str>| Literal {
+        language: language.map(|language| language.as_bytes().to_vec()),
+        ..literal(datatype, "Agent")
+    };
+    let invalid_languages = vec![
+        with_language(vocab::RDF_LANG_STRING, None),
+        with_language(vocab::RDF_LANG_STRING, Some("DE")),
+        with_language(vocab::RDF_LANG_STRING, Some("")),
+        with_language(vocab::XSD_STRING, Some("de")),
+    ];
+    for literal in invalid_languages {
+        match LiteralValue::from_literal(&literal) {
+            Err(LiteralError::InvalidLanguage { .. }) => {}
+            other => panic!("{:?} decoded to {:?}", literal, other),
+        }
+    }
+    assert_eq!(
+        Err(LiteralError::MissingDatatype),
+        LiteralValue::from_literal(&Literal {
+            datatype: None,
+            ..with_language(vocab::RDF_LANG_STRING, Some("de"))
+        })
+    );
+
+    assert_eq!(
+        Err(LiteralError::UnknownDatatype(vocab::OWL_THING.to_vec())),
+        LiteralValue::from_literal(&literal(vocab::OWL_THING, "1"))
+    );
+    assert!(LiteralValue::Decimal("01.5".to_owned())
+        .to_literal()
+        .is_err());
+    assert!(LiteralValue::Integer("+1".to_owned()).to_literal().is_err());
+}
+
+#[test]
+fn literal_value_assertion_target() {
+    let value = LiteralValue::Integer("34".to_owned());
+    let assertion = DataPropertyAssertion::builder()
+        .subject(&b"alice".to_vec())
+        .property(&DataProperty::default())
+        .target(value.to_data().unwrap())
+        .build()
+        .unwrap();
+    let target = assertion.target.as_ref().unwrap();
+    assert_eq!(Ok(value), LiteralValue::from_data(target));
+
+    // the value is inline, not a reference to a Literal entity
+    let entity: Entity = assertion.clone().into();
+    assert_eq!(2, entity.iter_cid_fields().count());
+
+    let literal = LiteralValue::String("34".to_owned()).to_literal().unwrap();
+    assert_eq!(
+        Err(LiteralError::InvalidData(literal.cid_bytes())),
+        LiteralValue::from_data(&literal.cid_bytes())
+    );
+    assert_eq!(
+        Err(LiteralError::InvalidData(b"34".to_vec())),
+        LiteralValue::from_data(b"34")
+    );
+}
+
+/// ABI encoding of a tuple of dynamic `bytes` parameters.
+fn abi_encode(params: &[&[u8]]) -> Vec<u8> {
+    let word = |value: usize| {
+        let mut word = vec![0u8; 24];
+        word.extend_from_slice(&(value as u64).to_be_bytes());
+        word
+    };
+    let mut head = Vec::new();
+    let mut tail = Vec::new();
+    for param in params {
+        head.extend(word(params.len() * 32 + tail.len()));
+        tail.extend(word(param.len()));
+        tail.extend_from_slice(param);
+        while tail.len() % 32 != 0 {
+            tail.push(0);
+        }
+    }
+    head.extend(tail);
+    head
+}
+
+#[test]
+fn literal_language_codecs() {
+    let literal = LiteralValue::LangString {
+        text: "Haus".to_owned(),
+        lang: "de".to_owned(),
+    }
+    .to_literal()
+    .unwrap();
+    let untagged = LiteralValue::String("Haus".to_owned())
+        .to_literal()
+        .unwrap();
+    assert_ne!(literal.cid_bytes(), untagged.cid_bytes());
+
+    let mut protobuf = Vec::new();
+    literal.encode(&mut protobuf).unwrap();
+    assert_eq!(literal, Literal::decode(protobuf.as_slice()).unwrap());
+
+    let entity = EntityV0::Literal(literal.clone());
+    let mut compact = Vec::new();
+    entity.serialize(&mut compact).unwrap();
+    assert_eq!(Ok(entity), EntityV0::deserialize_strict(&compact));
+
+    let literal_entity: Entity = literal.clone().into();
+    let web3 = serde_json::to_value(FormatWeb3(literal_entity.clone())).unwrap();
+    assert_eq!("0x6465", web3["language"]);
+    let parsed: FormatWeb3<Entity> = serde_json::from_value(web3).unwrap();
+    assert_eq!(literal_entity, parsed.0);
+
+    let abi = abi_encode(&[vocab::RDF_LANG_STRING, b"Haus", b"de"]);
+    assert_eq!(literal, Literal::from_abiv2(&abi));
+}
+
+#[test]
+fn literal_from_legacy_abi() {
+    // payloads encoded before Literal had a language field
+    let abi = abi_encode(&[vocab::XSD_STRING, b"Haus"]);
+    let literal = Literal::from_abiv2(&abi);
+    assert_eq!(
+        LiteralValue::String("Haus".to_owned())
+            .to_literal()
+            .unwrap(),
+        literal
+    );
+    assert_eq!(None, literal.language);
+}
+
+#[test]
+fn literal_best_label() {
+    let mut annotations = vec![
+        vocab::label_in("Haus", "de"),
+        vocab::label_in("House", "en-GB"),
+        vocab::label_in("Maison", "fr"),
+        vocab::label("house"),
+        vocab::comment("A building"),
+    ];
+    let mut entities = BTreeMap::new();
+    for entity in ["de", "en-gb", "fr"]
+        .iter()
+        .map(|tag| vocab::language(tag))
+        .chain(annotations.iter().cloned())
+        .map(Entity::Annotation)
+    {
+        entities.insert(entity.to_bytes(), entity);
+    }
+
+    // a label whose language can't be resolved is skipped instead of being read as untagged
+    annotations.push(vocab::label_in("Casa", "es"));
+    entities.insert(annotations[5].cid_bytes(), annotations[5].clone().into());
+    let cids: Vec<Vec<u8>> = annotations.iter().map(|ann| ann.cid_bytes()).collect();
+
+    assert_eq!(4, label::labels(&cids, &entities).len());
+    let best = |preferred: &[&str]| label::best_label(&cids, &entities, preferred).unwrap();
+    assert_eq!("Haus", best(&["de"]).text);
+    assert_eq!("Haus", best(&["de-AT", "en"]).text);
+    assert_eq!("House", best(&["en"]).text);
+    assert_eq!("Maison", best(&["es", "fr"]).text);
+    assert_eq!(
+        Label {
+            text: "house".to_owned(),
+            language: None,
+        },
+        best(&["es"])
+    );
+    assert_eq!(None, label::best_label(&cids[4..], &entities, &["en"]));
+}
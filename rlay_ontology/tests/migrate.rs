@@ -0,0 +1,101 @@
+use multihash::Hash;
+use rlay_ontology::migrate::{migrate, CidScheme, HashScheme, MigrationError};
+use rlay_ontology::prelude::*;
+use rlay_ontology::vocab;
+
+#[test]
+fn migrate_current_scheme_matches_to_cid() {
+    let current = HashScheme::current();
+    for entity in vocab::entities() {
+        assert_eq!(entity.to_bytes(), current.cid_bytes(&entity));
+    }
+}
+
+#[test]
+fn migrate_to_sha2() {
+    let label = vocab::label("Organization");
+    let agent = Class::default();
+    let org = Class::builder()
+        .annotation(&label)
+        .super_class(&agent)
+        .build()
+        .unwrap();
+    let entities: Vec<Entity> = vec![
+        org.clone().into(),
+        label.clone().into(),
+        agent.clone().into(),
+    ];
+
+    let sha2 = HashScheme {
+        hash: Hash::SHA2256,
+        codec_offset: 0xc000,
+    };
+    let migration = migrate(&entities, &HashScheme::current(), &sha2).unwrap();
+
+    let new_label = sha2.cid_bytes(&label.clone().into());
+    let new_agent = sha2.cid_bytes(&agent.clone().into());
+    let mut new_org = org.clone();
+    new_org.annotations = vec![new_label.clone()];
+    new_org.super_class_expression = vec![new_agent.clone()];
+    let new_org: Entity = new_org.into();
+
+    // dependencies first
+    assert_eq!(new_org, migration.entities[2]);
+    assert_eq!(Some(&new_label), migration.mapping.get(&label.cid_bytes()));
+    assert_eq!(Some(&new_agent), migration.mapping.get(&agent.cid_bytes()));
+    assert_eq!(
+        Some(&sha2.cid_bytes(&new_org)),
+        migration.mapping.get(&org.cid_bytes())
+    );
+    assert_eq!(3, migration.to_string().lines().count());
+
+    // migrating back restores the original entities
+    let back = migrate(&migration.entities, &sha2, &HashScheme::current()).unwrap();
+    for entity in entities.iter() {
+        assert!(back.entities.contains(entity));
+    }
+}
+
+#[test]
+fn migrate_keeps_external_references() {
+    let external = b"external".to_vec();
+    let class = Class::builder().super_class(&external).build().unwrap();
+    let entities: Vec<Entity> = vec![class.clone().into()];
+
+    let codec_range = HashScheme {
+        hash: Hash::Keccak256,
+        codec_offset: 0xd000,
+    };
+    let migration = migrate(&entities, &HashScheme::current(), &codec_range).unwrap();
+    assert_eq!(entities, migration.entities);
+    assert_ne!(class.cid_bytes(), migration.mapping[&class.cid_bytes()]);
+}
+
+#[test]
+fn migrate_rejects_cycles() {
+    // with a scheme that ignores the references, two classes can reference each other
+    struct Constant;
+    impl CidScheme for Constant {
+        fn cid_bytes(&self, entity: &Entity) -> Vec<u8> {
+            match entity.get_field("annotations") {
+                Some(FieldValue::Array(annotations)) => annotations[0].clone(),
+                _ => vec![],
+            }
+        }
+    }
+    let a = Class::builder()
+        .annotation(&b"a".to_vec())
+        .super_class(&b"b".to_vec())
+        .build()
+        .unwrap();
+    let b = Class::builder()
+        .annotation(&b"b".to_vec())
+        .super_class(&b"a".to_vec())
+        .build()
+        .unwrap();
+    let entities: Vec<Entity> = vec![a.into(), b.into()];
+    assert_eq!(
+        Err(MigrationError::Cycle(vec![b"a".to_vec(), b"b".to_vec()])),
+        migrate(&entities, &Constant, &HashScheme::current())
+    );
+}
@@ -0,0 +1,189 @@
+use multibase::Base;
+use rlay_ontology::index::AssertionIndex;
+use rlay_ontology::literal::LiteralValue;
+use rlay_ontology::prelude::*;
+use rlay_ontology::query::{query, Query, QueryError, Value};
+
+fn iri(cid: &[u8]) -> String {
+    format!("<{}>", multibase::encode(Base::Base58btc, cid))
+}
+
+struct Fixture {
+    index: AssertionIndex,
+    org: Vec<u8>,
+    employer: Vec<u8>,
+    label: Vec<u8>,
+    age: Vec<u8>,
+}
+
+fn integer(value: &str) -> LiteralValue {
+    LiteralValue::Integer(value.to_owned())
+}
+
+fn fixture() -> Fixture {
+    let org = Class::default().cid_bytes();
+    let employer = ObjectProperty::default().cid_bytes();
+    let label = AnnotationProperty::default().cid_bytes();
+    let age = DataProperty::default().cid_bytes();
+
+    let class_assertion = |subject: &[u8]| -> Entity {
+        ClassAssertion::builder()
+            .subject(subject)
+            .class(&org)
+            .build()
+            .unwrap()
+            .into()
+    };
+    let employment = |subject: &[u8], target: &[u8]| -> Entity {
+        ObjectPropertyAssertion::builder()
+            .subject(subject)
+            .property(&employer)
+            .target(target)
+            .build()
+            .unwrap()
+            .into()
+    };
+    let labelled = |subject: &[u8], value: &str| -> Entity {
+        AnnotationAssertion::builder()
+            .subject(subject)
+            .property(&label)
+            .value(value)
+            .build()
+            .unwrap()
+            .into()
+    };
+    let aged = |subject: &[u8], value: LiteralValue| -> Entity {
+        DataPropertyAssertion::builder()
+            .subject(subject)
+            .property(&age)
+            .target(value.to_data().unwrap())
+            .build()
+            .unwrap()
+            .into()
+    };
+
+    let entities = vec![
+        class_assertion(b"alice"),
+        class_assertion(b"bob"),
+        class_assertion(b"carol"),
+        employment(b"alice", b"acme"),
+        employment(b"bob", b"initech"),
+        employment(b"carol", b"acme"),
+        labelled(b"acme", "Acme"),
+        labelled(b"initech", "Initech"),
+        aged(b"alice", integer("34")),
+        aged(b"carol", integer("9")),
+        aged(b"dave", integer("9007199254740993")),
+        aged(b"erin", LiteralValue::Decimal("9.5".to_owned())),
+        aged(b"frank", LiteralValue::String("34".to_owned())),
+    ];
+
+    Fixture {
+        index: AssertionIndex::from_entities(&entities),
+        org,
+        employer,
+        label,
+        age,
+    }
+}
+
+#[test]
+fn query_basic_graph_pattern() {
+    let f = fixture();
+    let text = format!(
+        "SELECT ?person WHERE {{ ?person a {} . ?person {} ?company . ?company {} \"Acme\" . }}",
+        iri(&f.org),
+        iri(&f.employer),
+        iri(&f.label)
+    );
+
+    let table = query(&f.index, &text).unwrap();
+    assert_eq!(vec!["person".to_owned()], table.columns);
+    assert_eq!(
+        vec![
+            vec![Value::Cid(b"alice".to_vec())],
+            vec![Value::Cid(b"carol".to_vec())],
+        ],
+        table.rows
+    );
+}
+
+#[test]
+fn query_filter_and_limit() {
+    let f = fixture();
+    let text = format!(
+        "SELECT * WHERE {{ ?person {} ?age . FILTER(?age > 10) }}",
+        iri(&f.age)
+    );
+    let table = query(&f.index, &text).unwrap();
+    assert_eq!(vec!["person".to_owned(), "age".to_owned()], table.columns);
+    assert_eq!(
+        vec![
+            vec![Value::Cid(b"alice".to_vec()), Value::Literal(integer("34"))],
+            vec![
+                Value::Cid(b"dave".to_vec()),
+                Value::Literal(integer("9007199254740993"))
+            ],
+        ],
+        table.rows
+    );
+
+    let text = format!(
+        "SELECT ?person WHERE {{ ?person a {} }} LIMIT 2",
+        iri(&f.org)
+    );
+    assert_eq!(2, query(&f.index, &text).unwrap().rows.len());
+}
+
+#[test]
+fn query_filter_compares_by_datatype() {
+    let f = fixture();
+    let people = |filter: &str| -> Vec<Value> {
+        let text = format!(
+            "SELECT ?person WHERE {{ ?person {} ?age . FILTER({}) }}",
+            iri(&f.age),
+            filter
+        );
+        let mut people: Vec<Value> = query(&f.index, &text)
+            .unwrap()
+            .rows
+            .into_iter()
+            .map(|mut row| row.remove(0))
+            .collect();
+        people.sort_by_key(|value| value.as_bytes().unwrap().to_vec());
+        people
+    };
+    let person = |name: &str| Value::Cid(name.as_bytes().to_vec());
+
+    // integers beyond the precision of a double
+    assert_eq!(vec![person("dave")], people("?age > 9007199254740992"));
+    assert_eq!(
+        vec![person("alice"), person("dave"), person("erin")],
+        people("?age > 9.00")
+    );
+    assert_eq!(vec![person("carol"), person("erin")], people("?age < 010"));
+    // the string "34" is not the number 34
+    assert_eq!(vec![person("alice")], people("?age = 34"));
+    assert_eq!(vec![person("frank")], people("?age = \"34\""));
+    assert!(people("?age != \"x\"").contains(&person("frank")));
+    assert!(!people("?age != \"x\"").contains(&person("alice")));
+
+    let text = format!("SELECT ?person WHERE {{ ?person {} 9.0 }}", iri(&f.age));
+    assert_eq!(1, query(&f.index, &text).unwrap().rows.len());
+}
+
+#[test]
+fn query_errors() {
+    assert_eq!(
+        Err(QueryError::UnknownVariable("y".to_owned())),
+        Query::parse("SELECT ?y WHERE { ?x ?p ?o }")
+    );
+    assert_eq!(
+        Err(QueryError::UnexpectedEnd),
+        Query::parse("SELECT ?x WHERE { ?x ?p ?o")
+    );
+    match Query::parse("SELECT ?x WHERE { ?x ?p ?o } LIMIT ?x") {
+        Err(QueryError::UnexpectedToken { offset, .. }) => assert_eq!(35, offset),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
@@ -0,0 +1,259 @@
+#![cfg(feature = "reasoner")]
+use rlay_ontology::prelude::*;
+use rlay_ontology::reasoner::{Concept, Reasoner};
+use rlay_ontology::vocab;
+
+fn class(name: &str) -> Class {
+    Class::builder()
+        .annotation(&name.as_bytes().to_vec())
+        .build()
+        .unwrap()
+}
+
+fn cid<E: Into<Entity>>(entity: E) -> Vec<u8> {
+    entity.into().to_bytes()
+}
+
+#[test]
+fn reasoner_told_subsumption_from_entities() {
+    let thing = class("thing");
+    let agent = Class::builder()
+        .annotation(&b"agent".to_vec())
+        .super_class(&thing)
+        .build()
+        .unwrap();
+    let org = Class::builder()
+        .annotation(&b"org".to_vec())
+        .super_class(&agent)
+        .build()
+        .unwrap();
+
+    let entities: Vec<Entity> = vec![thing.clone().into(), agent.into(), org.clone().into()];
+    let classification = Reasoner::from_entities(&entities).classify();
+
+    assert!(classification.is_subsumed_by(&cid(org.clone()), &cid(thing.clone())));
+    assert!(!classification.is_subsumed_by(&cid(thing), &cid(org)));
+}
+
+#[test]
+fn reasoner_conjunction() {
+    let (person, employed, employee) = (class("person"), class("employed"), class("employee"));
+    let mut reasoner = Reasoner::new();
+    // Employee ≡ Person ⊓ Employed
+    reasoner.define(
+        &cid(employee.clone()),
+        &Concept::Intersection(vec![
+            Concept::named(&cid(person.clone())),
+            Concept::named(&cid(employed.clone())),
+        ]),
+    );
+    let manager = class("manager");
+    reasoner.add_subclass_of(
+        &Concept::named(&cid(manager.clone())),
+        &Concept::named(&cid(person)),
+    );
+    reasoner.add_subclass_of(
+        &Concept::named(&cid(manager.clone())),
+        &Concept::named(&cid(employed)),
+    );
+
+    let classification = reasoner.classify();
+    assert!(classification.is_subsumed_by(&cid(manager), &cid(employee)));
+}
+
+#[test]
+fn reasoner_existentials_with_property_hierarchy() {
+    let (parent, has_child) = (class("parent"), class("has_child"));
+    let person = class("person");
+    let mother = class("mother");
+    let has_daughter = ObjectProperty::builder()
+        .annotation(&b"has_daughter".to_vec())
+        .super_object_property(&has_child)
+        .build()
+        .unwrap();
+    let has_child = cid(has_child);
+
+    let mut reasoner = Reasoner::from_entities(&[has_daughter.clone().into()]);
+    // Parent ≡ ∃hasChild.Person
+    reasoner.define(
+        &cid(parent.clone()),
+        &Concept::some(&has_child, Concept::named(&cid(person.clone()))),
+    );
+    // Mother ⊑ ∃hasDaughter.Person
+    reasoner.add_subclass_of(
+        &Concept::named(&cid(mother.clone())),
+        &Concept::some(&cid(has_daughter), Concept::named(&cid(person))),
+    );
+
+    let classification = reasoner.classify();
+    assert!(classification.is_subsumed_by(&cid(mother.clone()), &cid(parent.clone())));
+    assert!(!classification.is_subsumed_by(&cid(parent), &cid(mother)));
+}
+
+#[test]
+fn reasoner_equivalents() {
+    let (a, b) = (class("a"), class("b"));
+    let mut reasoner = Reasoner::new();
+    reasoner.add_subclass_of(
+        &Concept::named(&cid(a.clone())),
+        &Concept::named(&cid(b.clone())),
+    );
+    reasoner.add_subclass_of(
+        &Concept::named(&cid(b.clone())),
+        &Concept::named(&cid(a.clone())),
+    );
+
+    let classification = reasoner.classify();
+    assert!(classification.equivalents(&cid(a)).contains(&cid(b)));
+}
+
+#[test]
+fn reasoner_taxonomy_direct_parents() {
+    let (a, b, c, d) = (class("a"), class("b"), class("c"), class("d"));
+    let mut reasoner = Reasoner::new();
+    // A ⊑ B ⊑ C, with D ≡ C
+    for (sub, sup) in [(&a, &b), (&b, &c), (&c, &d), (&d, &c)].iter() {
+        reasoner.add_subclass_of(
+            &Concept::named(&cid((*sub).clone())),
+            &Concept::named(&cid((*sup).clone())),
+        );
+    }
+
+    let classification = reasoner.classify();
+    let taxonomy = classification.to_taxonomy();
+    let top = classification.representative(&cid(c.clone()));
+    assert_eq!(top, classification.representative(&cid(d.clone())));
+    assert!(top == cid(c) || top == cid(d));
+
+    let set = |cids: Vec<Vec<u8>>| cids.into_iter().collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(
+        Some(&set(vec![cid(b.clone())])),
+        taxonomy.parents(&cid(a.clone()))
+    );
+    assert_eq!(Some(&set(vec![top.clone()])), taxonomy.parents(&cid(b)));
+    assert_eq!(Some(&set(vec![])), taxonomy.parents(&top));
+    assert_eq!(3, taxonomy.nodes().count());
+    assert!(taxonomy.is_subclass_of(&cid(a), &top));
+}
+
+#[test]
+fn reasoner_unsatisfiable_concepts() {
+    let (a, b, c, d, e, f) = (
+        class("a"),
+        class("b"),
+        class("c"),
+        class("d"),
+        class("e"),
+        class("f"),
+    );
+    let has_part = cid(class("has_part"));
+    let named = |class: &Class| Concept::named(&cid(class.clone()));
+    let mut reasoner = Reasoner::new();
+    // E ⊑ ∃hasPart.F is linked before F becomes unsatisfiable through F ⊑ A
+    reasoner.add_subclass_of(&named(&e), &Concept::some(&has_part, named(&f)));
+    reasoner.add_subclass_of(&named(&f), &named(&a));
+    // A ⊑ owl:Nothing, B ⊑ ∃hasPart.A, C ⊑ ∃hasPart.D
+    reasoner.add_subclass_of(&named(&a), &Concept::named(vocab::OWL_NOTHING));
+    reasoner.add_subclass_of(&named(&b), &Concept::some(&has_part, named(&a)));
+    reasoner.add_subclass_of(&named(&c), &Concept::some(&has_part, named(&d)));
+    reasoner.add_subclass_of(&named(&d), &Concept::named(vocab::OWL_THING));
+
+    let classification = reasoner.classify();
+    for unsatisfiable in [&a, &b, &e, &f].iter() {
+        let unsatisfiable = cid((*unsatisfiable).clone());
+        assert!(classification.is_unsatisfiable(&unsatisfiable));
+        assert!(classification.is_subsumed_by(&unsatisfiable, &cid(c.clone())));
+        assert!(classification
+            .equivalents(&unsatisfiable)
+            .contains(vocab::OWL_NOTHING));
+    }
+    for satisfiable in [&c, &d].iter() {
+        let satisfiable = cid((*satisfiable).clone());
+        assert!(!classification.is_unsatisfiable(&satisfiable));
+        assert!(classification.is_subsumed_by(&satisfiable, vocab::OWL_THING));
+    }
+    assert!(!classification.is_subsumed_by(&cid(c.clone()), &cid(d.clone())));
+
+    // the unsatisfiable concepts form a single node below the most specific satisfiable ones
+    let taxonomy = classification.to_taxonomy();
+    let bottom = classification.representative(vocab::OWL_NOTHING);
+    assert_eq!(bottom, classification.representative(&cid(b)));
+    let set = |cids: Vec<Vec<u8>>| cids.into_iter().collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(
+        Some(&set(vec![cid(c.clone()), cid(d.clone())])),
+        taxonomy.parents(&bottom)
+    );
+    assert_eq!(
+        Some(&set(vec![vocab::OWL_THING.to_vec()])),
+        taxonomy.parents(&cid(c))
+    );
+    assert_eq!(4, taxonomy.nodes().count());
+}
+
+#[test]
+fn reasoner_realization_via_superclasses() {
+    let agent = class("agent");
+    let org = Class::builder()
+        .annotation(&b"org".to_vec())
+        .super_class(&agent)
+        .build()
+        .unwrap();
+    let acme = b"acme".to_vec();
+    let assertion = ClassAssertion::builder()
+        .subject(&acme)
+        .class(&org)
+        .build()
+        .unwrap();
+
+    let entities: Vec<Entity> = vec![agent.clone().into(), org.clone().into(), assertion.into()];
+    let realization = Reasoner::from_entities(&entities).realize();
+
+    assert!(realization.is_instance_of(&acme, &cid(org.clone())));
+    assert!(!realization.is_inferred(&acme, &cid(org)));
+    assert!(realization.is_inferred(&acme, &cid(agent.clone())));
+
+    let inferred = realization.inferred_class_assertions();
+    assert_eq!(1, inferred.len());
+    let expected = ClassAssertion::builder()
+        .subject(&acme)
+        .class(&agent)
+        .build()
+        .unwrap();
+    assert_eq!(cid(expected.clone()), inferred[0].cid);
+    assert_eq!(expected, inferred[0].assertion);
+}
+
+#[test]
+fn reasoner_realization_via_existentials() {
+    let (parent, person) = (class("parent"), class("person"));
+    let has_child = cid(ObjectProperty::default());
+    let (alice, bob) = (b"alice".to_vec(), b"bob".to_vec());
+
+    let mut reasoner = Reasoner::new();
+    // Parent ≡ ∃hasChild.Person
+    reasoner.define(
+        &cid(parent.clone()),
+        &Concept::some(&has_child, Concept::named(&cid(person.clone()))),
+    );
+    reasoner.insert_entity(
+        &ObjectPropertyAssertion::builder()
+            .subject(&alice)
+            .property(&has_child)
+            .target(&bob)
+            .build()
+            .unwrap()
+            .into(),
+    );
+    reasoner.insert_entity(
+        &ClassAssertion::builder()
+            .subject(&bob)
+            .class(&person)
+            .build()
+            .unwrap()
+            .into(),
+    );
+
+    let realization = reasoner.realize();
+    assert!(realization.is_inferred(&alice, &cid(parent.clone())));
+    assert!(!realization.is_instance_of(&bob, &cid(parent)));
+}
@@ -0,0 +1,304 @@
+use rlay_ontology::prelude::*;
+use rlay_ontology::store::{EntityStore, FsStore, LogStore, StoreError};
+use rlay_ontology::vocab;
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rlay_store_{}_{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn fs_store_roundtrip() {
+    let dir = temp_dir("roundtrip");
+    let mut store = FsStore::open(&dir).unwrap();
+    let label: Entity = vocab::label("Organization").into();
+    let cid = label.to_bytes();
+
+    assert!(!store.has(&cid).unwrap());
+    assert_eq!(None, store.get(&cid).unwrap());
+    assert_eq!(cid, store.put(&label).unwrap());
+    assert_eq!(cid, store.put(&label).unwrap());
+    assert!(store.has(&cid).unwrap());
+    assert_eq!(Some(label), store.get(&cid).unwrap());
+
+    // sharded by the last two characters of the file name, without leftover temporary files
+    let path = store.path(&cid);
+    let name = path.file_name().unwrap().to_str().unwrap().to_owned();
+    assert_eq!(dir.join(&name[name.len() - 2..]).join(&name), path);
+    assert_eq!(1, fs::read_dir(path.parent().unwrap()).unwrap().count());
+
+    assert!(store.delete(&cid).unwrap());
+    assert!(!store.delete(&cid).unwrap());
+    assert!(!store.has(&cid).unwrap());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn fs_store_verifies_cid() {
+    let dir = temp_dir("verify");
+    let mut store = FsStore::open(&dir).unwrap();
+    let label: Entity = vocab::label("Organization").into();
+    let other: Entity = vocab::label("Agent").into();
+    let cid = store.put(&label).unwrap();
+
+    let mut bytes = Vec::new();
+    Into::<EntityV0>::into(other.clone())
+        .serialize(&mut bytes)
+        .unwrap();
+    fs::write(store.path(&cid), &bytes).unwrap();
+    match store.get(&cid) {
+        Err(StoreError::CidMismatch { expected, actual }) => {
+            assert_eq!(cid, expected);
+            assert_eq!(other.to_bytes(), actual);
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    fs::write(store.path(&cid), &bytes[..bytes.len() - 1]).unwrap();
+    match store.get(&cid) {
+        Err(StoreError::Decode { cid: decoded, .. }) => assert_eq!(cid, decoded),
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn fs_store_iter_kind() {
+    let dir = temp_dir("iter_kind");
+    let mut store = FsStore::open(&dir).unwrap();
+    let organization: Entity = vocab::label("Organization").into();
+    let agent: Entity = vocab::label("Agent").into();
+    let class: Entity = Class::builder()
+        .annotation(&vocab::label("Agent"))
+        .build()
+        .unwrap()
+        .into();
+    for entity in [&organization, &agent, &class].iter() {
+        store.put(entity).unwrap();
+    }
+
+    let mut expected = vec![
+        (organization.to_bytes(), organization),
+        (agent.to_bytes(), agent),
+    ];
+    expected.sort_by(|a, b| a.0.cmp(&b.0));
+    let annotations: Vec<(Vec<u8>, Entity)> = store
+        .iter_kind(EntityKind::Annotation)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(expected, annotations);
+
+    let classes: Vec<(Vec<u8>, Entity)> = store
+        .iter_kind(EntityKind::Class)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(vec![(class.to_bytes(), class)], classes);
+    assert_eq!(0, store.iter_kind(EntityKind::Individual).count());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+fn entities() -> Vec<Entity> {
+    vec![
+        vocab::label("Organization").into(),
+        Class::builder()
+            .annotation(&vocab::label("Organization"))
+            .build()
+            .unwrap()
+            .into(),
+        vocab::label("Agent").into(),
+    ]
+}
+
+#[test]
+fn log_store_insertion_order() {
+    let dir = temp_dir("log_order");
+    fs::create_dir_all(&dir).unwrap();
+    let entities = entities();
+    let mut store = LogStore::open(dir.join("entities.log")).unwrap();
+    for entity in entities.iter() {
+        store.put(entity).unwrap();
+    }
+    store.put(&entities[0]).unwrap();
+    assert!(store.delete(&entities[0].to_bytes()).unwrap());
+    assert!(!store.delete(&entities[0].to_bytes()).unwrap());
+    store.put(&entities[0]).unwrap();
+
+    let expected = vec![
+        (entities[1].to_bytes(), entities[1].clone()),
+        (entities[2].to_bytes(), entities[2].clone()),
+        (entities[0].to_bytes(), entities[0].clone()),
+    ];
+    let stored: Vec<(Vec<u8>, Entity)> = store.iter().collect::<Result<_, _>>().unwrap();
+    assert_eq!(expected, stored);
+    let annotations: Vec<(Vec<u8>, Entity)> = store
+        .iter_kind(EntityKind::Annotation)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(vec![expected[1].clone(), expected[2].clone()], annotations);
+
+    // the index is rebuilt from the log
+    drop(store);
+    let store = LogStore::open(dir.join("entities.log")).unwrap();
+    let stored: Vec<(Vec<u8>, Entity)> = store.iter().collect::<Result<_, _>>().unwrap();
+    assert_eq!(expected, stored);
+    assert_eq!(0, store.truncated());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn log_store_truncation_recovery() {
+    let dir = temp_dir("log_truncation");
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("entities.log");
+    let entities = entities();
+    let mut store = LogStore::open(&path).unwrap();
+    store.put(&entities[0]).unwrap();
+    store.put(&entities[1]).unwrap();
+    drop(store);
+
+    // interrupted write of the second record
+    let len = fs::metadata(&path).unwrap().len();
+    let file = fs::OpenOptions::new().write(true).open(&path).unwrap();
+    file.set_len(len - 3).unwrap();
+    drop(file);
+
+    let mut store = LogStore::open(&path).unwrap();
+    assert!(store.truncated() > 0);
+    assert!(store.has(&entities[0].to_bytes()).unwrap());
+    assert!(!store.has(&entities[1].to_bytes()).unwrap());
+
+    // the log continues after the last complete record
+    store.put(&entities[2]).unwrap();
+    drop(store);
+    let store = LogStore::open(&path).unwrap();
+    assert_eq!(0, store.truncated());
+    assert_eq!(
+        Some(entities[2].clone()),
+        store.get(&entities[2].to_bytes()).unwrap()
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn log_store_corruption_in_the_middle() {
+    let dir = temp_dir("log_corruption");
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("entities.log");
+    let mut store = LogStore::open(&path).unwrap();
+    for text in ["Agent", "Organization", "Company", "Person", "Place"].iter() {
+        store.put(&vocab::label(text).into()).unwrap();
+    }
+    drop(store);
+
+    // a damaged record followed by further records is not truncated away
+    let mut log = fs::read(&path).unwrap();
+    log[12] ^= 0xff;
+    fs::write(&path, &log).unwrap();
+    match LogStore::open(&path) {
+        Err(StoreError::InvalidRecord { offset }) => assert_eq!(0, offset),
+        result => panic!("unexpected result: {:?}", result),
+    }
+    assert_eq!(log, fs::read(&path).unwrap());
+
+    // a damaged last record is a torn tail
+    log[12] ^= 0xff;
+    let last = log.len() - 1;
+    log[last] ^= 0xff;
+    fs::write(&path, &log).unwrap();
+    let store = LogStore::open(&path).unwrap();
+    assert!(store.truncated() > 0);
+    assert_eq!(4, store.iter().count());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn log_store_checkpoint() {
+    let dir = temp_dir("log_checkpoint");
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("entities.log");
+    let entities = entities();
+    let mut store = LogStore::open(&path).unwrap();
+    store.put(&entities[0]).unwrap();
+    store.put(&entities[1]).unwrap();
+    store.checkpoint().unwrap();
+    store.delete(&entities[0].to_bytes()).unwrap();
+    store.put(&entities[2]).unwrap();
+    let expected: Vec<(Vec<u8>, Entity)> = store.iter().collect::<Result<_, _>>().unwrap();
+    let index_path = store.index_path();
+    drop(store);
+
+    // records after the checkpoint are replayed
+    let mut store = LogStore::open(&path).unwrap();
+    assert_eq!(
+        expected,
+        store.iter().collect::<Result<Vec<_>, _>>().unwrap()
+    );
+    store.rebuild_index().unwrap();
+    assert_eq!(
+        expected,
+        store.iter().collect::<Result<Vec<_>, _>>().unwrap()
+    );
+    drop(store);
+
+    // an index saved for a different log is ignored
+    let other_path = dir.join("other.log");
+    let mut other = LogStore::open(&other_path).unwrap();
+    other.put(&entities[2]).unwrap();
+    other.put(&entities[1]).unwrap();
+    other.put(&entities[0]).unwrap();
+    let other_expected: Vec<(Vec<u8>, Entity)> = other.iter().collect::<Result<_, _>>().unwrap();
+    drop(other);
+    let saved_log = fs::read(&path).unwrap();
+    fs::copy(&other_path, &path).unwrap();
+    let store = LogStore::open(&path).unwrap();
+    assert_eq!(
+        other_expected,
+        store.iter().collect::<Result<Vec<_>, _>>().unwrap()
+    );
+    drop(store);
+    fs::write(&path, &saved_log).unwrap();
+
+    // a damaged index is ignored
+    let mut index = fs::read(&index_path).unwrap();
+    index[0] ^= 0xff;
+    fs::write(&index_path, &index).unwrap();
+    let store = LogStore::open(&path).unwrap();
+    assert_eq!(
+        expected,
+        store.iter().collect::<Result<Vec<_>, _>>().unwrap()
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn stores_roundtrip_empty_optional_bytes() {
+    let dir = temp_dir("empty_optional_bytes");
+    let literal: Entity = Literal {
+        datatype: Some(vec![]),
+        ..Literal::default()
+    }
+    .into();
+    let cid = literal.to_bytes();
+
+    let mut fs_store = FsStore::open(dir.join("fs")).unwrap();
+    fs_store.put(&literal).unwrap();
+    assert_eq!(Some(literal.clone()), fs_store.get(&cid).unwrap());
+
+    let mut log_store = LogStore::open(dir.join("entities.log")).unwrap();
+    log_store.put(&literal).unwrap();
+    drop(log_store);
+    let log_store = LogStore::open(dir.join("entities.log")).unwrap();
+    assert_eq!(Some(literal), log_store.get(&cid).unwrap());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
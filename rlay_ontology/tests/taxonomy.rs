@@ -0,0 +1,89 @@
+use rlay_ontology::prelude::*;
+use rlay_ontology::taxonomy::Taxonomy;
+use std::collections::BTreeSet;
+
+fn subclass_of(name: &str, parents: &[&Class]) -> Class {
+    let mut builder = Class::builder().annotation(&name.as_bytes().to_vec());
+    for parent in parents {
+        builder = builder.super_class(*parent);
+    }
+    builder.build().unwrap()
+}
+
+fn cid(class: &Class) -> Vec<u8> {
+    let entity: Entity = class.clone().into();
+    entity.to_bytes()
+}
+
+fn set(classes: &[&Class]) -> BTreeSet<Vec<u8>> {
+    classes.iter().map(|class| cid(class)).collect()
+}
+
+#[test]
+fn taxonomy_transitive_queries() {
+    let thing = subclass_of("thing", &[]);
+    let agent = subclass_of("agent", &[&thing]);
+    let org = subclass_of("org", &[&agent]);
+    let person = subclass_of("person", &[&agent]);
+    let company = subclass_of("company", &[&org]);
+
+    let entities: Vec<Entity> = vec![&thing, &agent, &org, &person, &company]
+        .into_iter()
+        .map(|class| class.clone().into())
+        .collect();
+    let taxonomy = Taxonomy::from_entities(&entities);
+
+    assert_eq!(
+        &set(&[&org, &agent, &thing]),
+        taxonomy.ancestors(&cid(&company)).unwrap()
+    );
+    assert_eq!(
+        &set(&[&org, &person, &company]),
+        taxonomy.descendants(&cid(&agent)).unwrap()
+    );
+    assert_eq!(set(&[&thing]), taxonomy.roots());
+    assert_eq!(set(&[&person, &company]), taxonomy.leaves());
+    assert!(taxonomy.is_subclass_of(&cid(&company), &cid(&thing)));
+    assert!(!taxonomy.is_subclass_of(&cid(&person), &cid(&org)));
+    assert_eq!(
+        set(&[&agent]),
+        taxonomy.least_common_ancestors(&cid(&company), &cid(&person))
+    );
+    assert_eq!(
+        set(&[&org]),
+        taxonomy.least_common_ancestors(&cid(&company), &cid(&org))
+    );
+}
+
+#[test]
+fn taxonomy_incremental_insertion() {
+    let thing = subclass_of("thing", &[]);
+    let agent = subclass_of("agent", &[&thing]);
+    let org = subclass_of("org", &[&agent]);
+
+    // insert bottom-up, so that the closure has to be propagated to existing descendants
+    let mut taxonomy = Taxonomy::new();
+    taxonomy.insert(&org);
+    assert_eq!(&set(&[&agent]), taxonomy.ancestors(&cid(&org)).unwrap());
+    taxonomy.insert(&agent);
+    taxonomy.insert(&thing);
+
+    assert_eq!(
+        &set(&[&agent, &thing]),
+        taxonomy.ancestors(&cid(&org)).unwrap()
+    );
+    assert_eq!(
+        &set(&[&agent, &org]),
+        taxonomy.descendants(&cid(&thing)).unwrap()
+    );
+    assert_eq!(set(&[&thing]), taxonomy.roots());
+}
+
+#[test]
+fn taxonomy_ignores_other_kinds() {
+    let mut taxonomy = Taxonomy::new();
+    let individual: Entity = Individual::default().into();
+
+    assert!(!taxonomy.insert_entity(&individual));
+    assert_eq!(0, taxonomy.nodes().count());
+}
@@ -0,0 +1,80 @@
+use rlay_ontology::prelude::*;
+use rlay_ontology::topo::{topo_sort, TopoError};
+use rlay_ontology::vocab;
+
+#[test]
+fn topo_sort_dependencies_first() {
+    let label = vocab::label("Organization");
+    let agent = Class::default();
+    let org = Class::builder()
+        .annotation(&label)
+        .super_class(&agent)
+        .build()
+        .unwrap();
+    let company = Class::builder().super_class(&org).build().unwrap();
+    let entities: Vec<Entity> = vec![
+        company.clone().into(),
+        org.clone().into(),
+        label.clone().into(),
+        agent.clone().into(),
+    ];
+
+    let order = topo_sort(&entities).unwrap();
+    let cids: Vec<Vec<u8>> = order.cids().cloned().collect();
+    let position = |cid: Vec<u8>| cids.iter().position(|other| *other == cid).unwrap();
+    assert_eq!(4, cids.len());
+    assert!(position(label.cid_bytes()) < position(org.cid_bytes()));
+    assert!(position(agent.cid_bytes()) < position(org.cid_bytes()));
+    assert_eq!(3, position(company.cid_bytes()));
+    // the label annotation references the rdfs:label property, which is not part of the batch
+    assert_eq!(
+        vec![vocab::RDFS_LABEL.to_vec()],
+        order.external.keys().cloned().collect::<Vec<_>>()
+    );
+
+    // the order does not depend on the order of the batch
+    let mut reversed = entities.clone();
+    reversed.reverse();
+    reversed.push(entities[0].clone());
+    assert_eq!(order, topo_sort(&reversed).unwrap());
+}
+
+#[test]
+fn topo_sort_external_references() {
+    let external = vocab::owl_thing();
+    let label = vocab::label("Agent");
+    let agent = Class::builder()
+        .annotation(&label)
+        .super_class(&external)
+        .build()
+        .unwrap();
+    let entities: Vec<Entity> = vec![agent.clone().into(), label.clone().into()];
+
+    let order = topo_sort(&entities).unwrap();
+    assert_eq!(
+        vec![label.cid_bytes(), agent.cid_bytes()],
+        order.cids().cloned().collect::<Vec<_>>()
+    );
+    assert_eq!(2, order.external.len());
+    assert!(order.external[vocab::OWL_THING].contains(&agent.cid_bytes()));
+    assert!(order.external[vocab::RDFS_LABEL].contains(&label.cid_bytes()));
+}
+
+#[test]
+fn topo_sort_keyed_cycle() {
+    let a = Class::builder()
+        .super_class(&b"b".to_vec())
+        .build()
+        .unwrap();
+    let b = Class::builder()
+        .super_class(&b"a".to_vec())
+        .build()
+        .unwrap();
+    let mut entities = std::collections::BTreeMap::new();
+    entities.insert(b"a".to_vec(), a.into());
+    entities.insert(b"b".to_vec(), b.into());
+    assert_eq!(
+        Err(TopoError::Cycle(vec![b"a".to_vec(), b"b".to_vec()])),
+        rlay_ontology::topo::topo_sort_keyed(entities)
+    );
+}
@@ -0,0 +1,72 @@
+use rlay_ontology::prelude::*;
+
+#[test]
+fn valid_annotation() {
+    let annotation = Annotation {
+        property: b"\x01\x02\x03".to_vec(),
+        value: b"\x04".to_vec(),
+        ..Annotation::default()
+    };
+
+    assert_eq!(Ok(()), annotation.validate());
+}
+
+#[test]
+fn required_field_empty() {
+    let assertion = ClassAssertion {
+        subject: Some(b"\x01".to_vec()),
+        ..ClassAssertion::default()
+    };
+
+    let expected = vec![Violation {
+        field: "class",
+        kind: ViolationKind::Empty,
+    }];
+    assert_eq!(Err(expected), assertion.validate());
+}
+
+#[test]
+fn min_cardinality() {
+    let intersection = DataIntersectionOf {
+        datatypes: vec![b"\x01".to_vec()],
+        ..DataIntersectionOf::default()
+    };
+
+    let expected = vec![Violation {
+        field: "datatypes",
+        kind: ViolationKind::TooFewElements { min: 2, actual: 1 },
+    }];
+    assert_eq!(Err(expected), intersection.validate());
+
+    let one_of = DataOneOf::default();
+    let expected = vec![Violation {
+        field: "values",
+        kind: ViolationKind::TooFewElements { min: 1, actual: 0 },
+    }];
+    assert_eq!(Err(expected), one_of.validate());
+}
+
+#[test]
+fn entity_reports_all_violations() {
+    let entity: Entity = Annotation {
+        annotations: vec![b"\x01".to_vec(), vec![]],
+        ..Annotation::default()
+    }
+    .into();
+
+    let expected = vec![
+        Violation {
+            field: "annotations",
+            kind: ViolationKind::EmptyElement { index: 1 },
+        },
+        Violation {
+            field: "property",
+            kind: ViolationKind::Empty,
+        },
+        Violation {
+            field: "value",
+            kind: ViolationKind::Empty,
+        },
+    ];
+    assert_eq!(Err(expected), entity.validate());
+}
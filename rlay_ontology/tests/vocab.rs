@@ -0,0 +1,49 @@
+use rlay_ontology::prelude::*;
+use rlay_ontology::vocab::{self, *};
+use std::collections::BTreeSet;
+
+#[test]
+fn vocab_cids_pinned() {
+    assert_eq!(RDFS_LABEL, rdfs_label().cid_bytes().as_slice());
+    assert_eq!(RDFS_COMMENT, rdfs_comment().cid_bytes().as_slice());
+    assert_eq!(RDFS_SEE_ALSO, rdfs_see_also().cid_bytes().as_slice());
+    assert_eq!(DCTERMS_LANGUAGE, dcterms_language().cid_bytes().as_slice());
+    assert_eq!(OWL_THING, owl_thing().cid_bytes().as_slice());
+    assert_eq!(OWL_NOTHING, owl_nothing().cid_bytes().as_slice());
+    assert_eq!(RDF_LANG_STRING, rdf_lang_string().cid_bytes().as_slice());
+    assert_eq!(XSD_STRING, xsd_string().cid_bytes().as_slice());
+    assert_eq!(XSD_INTEGER, xsd_integer().cid_bytes().as_slice());
+    assert_eq!(XSD_DECIMAL, xsd_decimal().cid_bytes().as_slice());
+    assert_eq!(XSD_BOOLEAN, xsd_boolean().cid_bytes().as_slice());
+    assert_eq!(XSD_DATE_TIME, xsd_date_time().cid_bytes().as_slice());
+    assert_eq!(
+        XSD_BASE64_BINARY,
+        xsd_base64_binary().cid_bytes().as_slice()
+    );
+}
+
+#[test]
+fn vocab_entities_distinct() {
+    let entities = vocab::entities();
+    let cids: BTreeSet<Vec<u8>> = entities.iter().map(|entity| entity.to_bytes()).collect();
+    assert_eq!(entities.len(), cids.len());
+    assert!(cids.contains(OWL_THING));
+    assert!(cids.contains(XSD_DATE_TIME));
+    assert!(cids.contains(&iri_annotation(OWL_THING_IRI).cid_bytes()));
+}
+
+#[test]
+fn vocab_label() {
+    let annotation = label("Organization");
+    assert_eq!(RDFS_LABEL.to_vec(), annotation.property);
+    assert_eq!(b"Organization".to_vec(), annotation.value);
+    assert_eq!(RDFS_COMMENT.to_vec(), comment("An organization").property);
+
+    let organization = Class::builder().annotation(&annotation).build().unwrap();
+    assert_eq!(vec![annotation.cid_bytes()], organization.annotations);
+
+    let tagged = label_in("Organisation", "en-GB");
+    assert_eq!(b"Organisation".to_vec(), tagged.value);
+    assert_eq!(b"en-gb".to_vec(), language("en-GB").value);
+    assert_eq!(vec![language("en-gb").cid_bytes()], tagged.annotations);
+}
@@ -0,0 +1,160 @@
+use super::*;
+use crate::core::get_data_fields;
+
+pub fn build_file(src_path: &str, out_path: &str) {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let dest_path = Path::new(&out_dir).join(out_path);
+
+    let mut intermediate_file = File::open(src_path).expect("file not found");
+
+    let mut intermediate_contents = String::new();
+    intermediate_file
+        .read_to_string(&mut intermediate_contents)
+        .unwrap();
+    let intermediate = parse_intermediate_contents(&intermediate_contents);
+
+    let mut out_file = File::create(&dest_path).unwrap();
+
+    let kinds = intermediate.kinds;
+    for raw_kind in kinds.iter() {
+        let kind_name = &raw_kind.name;
+        let fields = raw_kind.fields.clone();
+
+        write_impl_cid_reference(&mut out_file, kind_name);
+        write_builder(&mut out_file, kind_name, &fields);
+    }
+}
+
+/// Name of the builder method for a field, e.g. `annotation` for the array field `annotations`
+/// and `super_class` for `superClassExpression`.
+fn builder_method_ident(field: &Field) -> syn::Ident {
+    let mut name = field.name.to_snake_case();
+    if field.is_array_kind() && name.ends_with('s') {
+        name.pop();
+    }
+    if name.starts_with("super_") && name.ends_with("_expression") {
+        name.truncate(name.len() - "_expression".len());
+    }
+    syn::parse_str(&name).unwrap()
+}
+
+fn write_impl_cid_reference<W: Write>(writer: &mut W, kind_name: &str) {
+    let kind_ty: syn::Type = syn::parse_str(kind_name).unwrap();
+    let trait_impl: TokenStream = parse_quote! {
+        impl CidReference for #kind_ty {
+            fn cid_bytes(&self) -> Vec<u8> {
+                self.to_cid().unwrap().to_bytes()
+            }
+        }
+    };
+    write!(writer, "{}", trait_impl).unwrap();
+}
+
+fn write_builder<W: Write>(writer: &mut W, kind_name: &str, fields: &[Field]) {
+    let data_field_names: Vec<String> = get_data_fields(kind_name, fields)
+        .into_iter()
+        .map(|n| n.name)
+        .collect();
+
+    let kind_ty: syn::Type = syn::parse_str(kind_name).unwrap();
+    let builder_ty: syn::Type = syn::parse_str(&format!("{}Builder", kind_name)).unwrap();
+
+    let builder_fields: TokenStream = fields
+        .iter()
+        .map(|field| {
+            let field_ident = field.field_ident();
+            let tokens: TokenStream = match field.is_array_kind() {
+                true => parse_quote!(#field_ident: Vec<Vec<u8>>,),
+                false => parse_quote!(#field_ident: Option<Vec<u8>>,),
+            };
+            tokens
+        })
+        .collect();
+
+    let builder_methods: TokenStream = fields
+        .iter()
+        .map(|field| {
+            let field_ident = field.field_ident();
+            let method_ident = builder_method_ident(field);
+            let is_data_field = data_field_names.contains(&field.name);
+            let tokens: TokenStream = match (field.is_array_kind(), is_data_field) {
+                (true, true) => parse_quote! {
+                    pub fn #method_ident<V: Into<Vec<u8>>>(mut self, value: V) -> Self {
+                        self.#field_ident.push(value.into());
+                        self
+                    }
+                },
+                (true, false) => parse_quote! {
+                    pub fn #method_ident<R: CidReference + ?Sized>(mut self, reference: &R) -> Self {
+                        self.#field_ident.push(reference.cid_bytes());
+                        self
+                    }
+                },
+                (false, true) => parse_quote! {
+                    pub fn #method_ident<V: Into<Vec<u8>>>(mut self, value: V) -> Self {
+                        self.#field_ident = Some(value.into());
+                        self
+                    }
+                },
+                (false, false) => parse_quote! {
+                    pub fn #method_ident<R: CidReference + ?Sized>(mut self, reference: &R) -> Self {
+                        self.#field_ident = Some(reference.cid_bytes());
+                        self
+                    }
+                },
+            };
+            tokens
+        })
+        .collect();
+
+    let build_fields: TokenStream = fields
+        .iter()
+        .map(|field| {
+            let field_ident = field.field_ident();
+            let field_name = &field.name;
+            let tokens: TokenStream = match (field.is_array_kind(), field.required) {
+                (true, _) => parse_quote!(#field_ident: self.#field_ident,),
+                (false, true) => parse_quote! {
+                    #field_ident: self
+                        .#field_ident
+                        .ok_or(BuildError::MissingField(#field_name))?,
+                },
+                (false, false) => parse_quote!(#field_ident: self.#field_ident,),
+            };
+            tokens
+        })
+        .collect();
+
+    let builder_doc = format!("Builder for [`{}`].", kind_name);
+    let builder_struct: TokenStream = parse_quote! {
+        #[doc = #builder_doc]
+        #[derive(Debug, Clone, Default)]
+        pub struct #builder_ty {
+            #builder_fields
+        }
+    };
+    write!(writer, "{}", builder_struct).unwrap();
+
+    let builder_impl: TokenStream = parse_quote! {
+        impl #builder_ty {
+            #builder_methods
+
+            /// Builds the entity, failing if a required field has not been set.
+            pub fn build(self) -> Result<#kind_ty, BuildError> {
+                Ok(#kind_ty {
+                    #build_fields
+                })
+            }
+        }
+    };
+    write!(writer, "{}", builder_impl).unwrap();
+
+    let kind_impl: TokenStream = parse_quote! {
+        impl #kind_ty {
+            pub fn builder() -> #builder_ty {
+                #builder_ty::default()
+            }
+        }
+    };
+    write!(writer, "{}", kind_impl).unwrap();
+}
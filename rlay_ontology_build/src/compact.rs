@@ -27,6 +27,7 @@ fn write_variant_format_compact<W: Write>(writer: &mut W, kind_name: &str, field
     write_format_variant_wrapper(writer, "Compact", kind_name, fields, true);
     write_format_compact_impl_serialize(writer, kind_name, fields);
     write_format_compact_impl_deserialize(writer, kind_name, fields);
+    write_format_compact_impl_strict(writer, kind_name, fields);
 }
 
 fn write_format_compact_impl_serialize<W: Write>(
@@ -164,3 +165,28 @@ fn write_format_compact_impl_deserialize<W: Write>(
 
     write!(writer, "{}", trait_impl).unwrap();
 }
+
+fn write_format_compact_impl_strict<W: Write>(writer: &mut W, kind_name: &str, fields: &[Field]) {
+    // keys as they are emitted by the SerializeHelper, in declaration order
+    let field_keys: Vec<String> = fields
+        .iter()
+        .map(|field| field.field_ident().to_string())
+        .collect();
+    let array_keys: Vec<String> = fields
+        .iter()
+        .filter(|field| field.is_array_kind())
+        .map(|field| field.field_ident().to_string())
+        .collect();
+
+    let wrapper_ty: syn::Type = syn::parse_str(&format!("{}FormatCompact", kind_name)).unwrap();
+    let type_impl: TokenStream = parse_quote! {
+        #[cfg(feature = "std")]
+        impl #wrapper_ty {
+            /// Decodes the entity, only accepting the exact bytes the serializer would produce.
+            pub fn from_slice_strict(bytes: &[u8]) -> Result<Self, canonical::CanonicalError> {
+                canonical::decode_compact_strict(bytes, &[#(#field_keys),*], &[#(#array_keys),*])
+            }
+        }
+    };
+    write!(writer, "{}", type_impl).unwrap();
+}
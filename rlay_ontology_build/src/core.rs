@@ -58,6 +58,10 @@ pub fn build_macros_applied_file(src_path: &str, out_path: &str) {
         write_impl_cid_field_names(&mut out_file, kind_name, &raw_kind.fields);
         // impl DataFieldNames
         write_impl_data_field_names(&mut out_file, kind_name, &raw_kind.fields);
+        // impl Validate
+        write_impl_validate(&mut out_file, kind_name, &raw_kind.fields);
+        // field access by name
+        write_impl_field_access(&mut out_file, kind_name, &raw_kind.fields);
 
         write!(
             out_file,
@@ -87,7 +91,169 @@ pub fn build_macros_applied_file(src_path: &str, out_path: &str) {
     };
     write!(out_file, "{}", macro_call_with_entity_kinds,).unwrap();
     write_entity_kind(&mut out_file, kind_names.clone(), kind_ids.clone());
+    write_kind_schemas(&mut out_file, &kinds);
     write_entity(&mut out_file, kind_names.clone());
+    write_assertion_complements(&mut out_file, &kinds);
+    write_impl_assertion(&mut out_file, &kinds);
+}
+
+fn write_impl_assertion<W: Write>(writer: &mut W, kinds: &[Kind]) {
+    let assertion_kinds: Vec<&Kind> = get_assertion_pairs(kinds)
+        .into_iter()
+        .flat_map(|(positive, negative)| vec![positive, negative])
+        .collect();
+
+    // Accessor for the first of the candidate fields that the kind has.
+    let accessor = |kind: &Kind, candidates: &[&str]| -> TokenStream {
+        let field = kind
+            .fields
+            .iter()
+            .find(|field| candidates.contains(&field.name.as_ref()));
+        match field {
+            Some(field) => {
+                let field_ident = field.field_ident();
+                match field.required {
+                    true => parse_quote!(Some(&self.#field_ident)),
+                    false => parse_quote!(self.#field_ident.as_ref()),
+                }
+            }
+            None => parse_quote!(None),
+        }
+    };
+
+    for kind in assertion_kinds.iter() {
+        let kind_ty: syn::Type = syn::parse_str(&kind.name).unwrap();
+        let subject = accessor(kind, &["subject"]);
+        let predicate = accessor(kind, &["property", "class"]);
+        let object = accessor(kind, &["target", "value"]);
+
+        let trait_impl: TokenStream = parse_quote! {
+            impl Assertion for #kind_ty {
+                fn subject(&self) -> Option<&Vec<u8>> {
+                    #subject
+                }
+
+                fn predicate(&self) -> Option<&Vec<u8>> {
+                    #predicate
+                }
+
+                fn object(&self) -> Option<&Vec<u8>> {
+                    #object
+                }
+            }
+        };
+        write!(writer, "{}", trait_impl).unwrap();
+    }
+
+    let variants: Vec<syn::Type> = assertion_kinds
+        .iter()
+        .map(|kind| syn::parse_str(&kind.name).unwrap())
+        .collect();
+    let entity_impl: TokenStream = parse_quote! {
+        impl Entity {
+            /// Returns the entity as an assertion triple, if it is an assertion.
+            pub fn as_assertion(&self) -> Option<&dyn Assertion> {
+                match &self {
+                    #(Entity::#variants(ent) => Some(ent),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+    write!(writer, "{}", entity_impl).unwrap();
+}
+
+/// Pairs of positive and negative assertion kinds, found by the `Negative` prefix of the name
+/// of the negative kind.
+fn get_assertion_pairs(kinds: &[Kind]) -> Vec<(&Kind, &Kind)> {
+    kinds
+        .iter()
+        .filter_map(|positive| {
+            let negative_name = format!("Negative{}", positive.name);
+            kinds
+                .iter()
+                .find(|kind| kind.name == negative_name)
+                .map(|negative| (positive, negative))
+        })
+        .collect()
+}
+
+fn write_assertion_complements<W: Write>(writer: &mut W, kinds: &[Kind]) {
+    let pairs = get_assertion_pairs(kinds);
+
+    for (positive, negative) in pairs.iter() {
+        let field_names = |kind: &Kind| -> Vec<String> {
+            kind.fields.iter().map(|field| field.name.clone()).collect()
+        };
+        assert_eq!(
+            field_names(positive),
+            field_names(negative),
+            "Assertion kinds {} and {} need to have the same fields",
+            positive.name,
+            negative.name
+        );
+
+        for (kind, complement) in [(positive, negative), (negative, positive)].iter() {
+            let kind_ty: syn::Type = syn::parse_str(&kind.name).unwrap();
+            let complement_ty: syn::Type = syn::parse_str(&complement.name).unwrap();
+            let complement_fields: TokenStream = kind
+                .fields
+                .iter()
+                .map(|field| {
+                    let field_ident = field.field_ident();
+                    let tokens: TokenStream = match field.name.as_ref() {
+                        "annotations" => parse_quote!(#field_ident: vec![],),
+                        _ => parse_quote!(#field_ident: self.#field_ident.clone(),),
+                    };
+                    tokens
+                })
+                .collect();
+
+            let trait_impl: TokenStream = parse_quote! {
+                impl GetAssertionComplement for #kind_ty {
+                    type Complement = #complement_ty;
+
+                    fn get_assertion_complement(&self) -> Self::Complement {
+                        #complement_ty {
+                            #complement_fields
+                        }
+                    }
+                }
+            };
+            write!(writer, "{}", trait_impl).unwrap();
+        }
+    }
+
+    let positive_variants: Vec<syn::Type> = pairs
+        .iter()
+        .map(|(positive, _)| syn::parse_str(&positive.name).unwrap())
+        .collect();
+    let negative_variants: Vec<syn::Type> = pairs
+        .iter()
+        .map(|(_, negative)| syn::parse_str(&negative.name).unwrap())
+        .collect();
+    let entity_impl: TokenStream = parse_quote! {
+        impl Entity {
+            pub fn polarity(&self) -> Polarity {
+                match &self {
+                    #(Entity::#positive_variants(_) => Polarity::Positive,)*
+                    #(Entity::#negative_variants(_) => Polarity::Negative,)*
+                    _ => Polarity::NotAnAssertion,
+                }
+            }
+
+            /// Returns the assertion with the opposite polarity and without annotations, if the
+            /// entity is an assertion.
+            pub fn assertion_complement(&self) -> Option<Entity> {
+                match &self {
+                    #(Entity::#positive_variants(ent) => Some(ent.get_assertion_complement().into()),)*
+                    #(Entity::#negative_variants(ent) => Some(ent.get_assertion_complement().into()),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+    write!(writer, "{}", entity_impl).unwrap();
 }
 
 fn get_cid_fields(kind_name: &str, fields: &[Field]) -> Vec<Field> {
@@ -104,6 +270,9 @@ fn get_cid_fields(kind_name: &str, fields: &[Field]) -> Vec<Field> {
             if kind_name == "NegativeDataPropertyAssertion" && field.name == "target" {
                 return false;
             }
+            if kind_name == "Literal" && field.name == "language" {
+                return false;
+            }
             true
         })
         .collect()
@@ -232,7 +401,7 @@ fn write_impl_cid_fields<W: Write>(writer: &mut W, kind_name: &str, fields: &[Fi
     write!(writer, "{}", impl_cid_fields).unwrap();
 }
 
-fn get_data_fields(kind_name: &str, fields: &[Field]) -> Vec<Field> {
+pub fn get_data_fields(kind_name: &str, fields: &[Field]) -> Vec<Field> {
     fields
         .to_owned()
         .into_iter()
@@ -252,6 +421,9 @@ fn get_data_fields(kind_name: &str, fields: &[Field]) -> Vec<Field> {
             if kind_name == "NegativeDataPropertyAssertion" && field.name == "target" {
                 return true;
             }
+            if kind_name == "Literal" && field.name == "language" {
+                return true;
+            }
             false
         })
         .collect()
@@ -381,12 +553,130 @@ fn write_impl_data_fields<W: Write>(writer: &mut W, kind_name: &str, fields: &[F
     write!(writer, "{}", impl_cid_fields).unwrap();
 }
 
+fn write_impl_validate<W: Write>(writer: &mut W, kind_name: &str, fields: &[Field]) {
+    let kind_ty: syn::Type = syn::parse_str(kind_name).unwrap();
+
+    let field_checks: Vec<TokenStream> = fields
+        .iter()
+        .map(|field| {
+            let field_ident = field.field_ident();
+            let field_name = &field.name;
+            let stmt: TokenStream = match (field.is_array_kind(), field.required) {
+                (true, _) => {
+                    let min_cardinality_check: TokenStream = match field.minCardinality {
+                        Some(min) => parse_quote! {
+                            if self.#field_ident.len() < #min {
+                                violations.push(Violation {
+                                    field: #field_name,
+                                    kind: ViolationKind::TooFewElements {
+                                        min: #min,
+                                        actual: self.#field_ident.len(),
+                                    },
+                                });
+                            }
+                        },
+                        None => TokenStream::new(),
+                    };
+                    parse_quote! {
+                        #min_cardinality_check
+                        for (index, element) in self.#field_ident.iter().enumerate() {
+                            if element.is_empty() {
+                                violations.push(Violation {
+                                    field: #field_name,
+                                    kind: ViolationKind::EmptyElement { index },
+                                });
+                            }
+                        }
+                    }
+                }
+                (false, true) => parse_quote! {
+                    if self.#field_ident.is_empty() {
+                        violations.push(Violation {
+                            field: #field_name,
+                            kind: ViolationKind::Empty,
+                        });
+                    }
+                },
+                (false, false) => parse_quote! {
+                    if let Some(value) = &self.#field_ident {
+                        if value.is_empty() {
+                            violations.push(Violation {
+                                field: #field_name,
+                                kind: ViolationKind::Empty,
+                            });
+                        }
+                    }
+                },
+            };
+            stmt
+        })
+        .collect();
+
+    let impl_validate: TokenStream = parse_quote! {
+        // a minimum cardinality of 1 is checked as `len() < 1`
+        #[allow(clippy::len_zero)]
+        impl Validate for #kind_ty {
+            fn validate(&self) -> Result<(), Vec<Violation>> {
+                let mut violations = Vec::new();
+
+                #(#field_checks)*
+
+                match violations.is_empty() {
+                    true => Ok(()),
+                    false => Err(violations),
+                }
+            }
+        }
+    };
+    write!(writer, "{}", impl_validate).unwrap();
+}
+
+fn write_impl_field_access<W: Write>(writer: &mut W, kind_name: &str, fields: &[Field]) {
+    let kind_ty: syn::Type = syn::parse_str(kind_name).unwrap();
+    let field_names: Vec<&str> = fields.iter().map(|n| n.name.as_str()).collect();
+    let field_idents: Vec<syn::Ident> = fields.iter().map(|n| n.field_ident()).collect();
+    let field_variants: Vec<syn::Ident> = fields
+        .iter()
+        .map(|field| {
+            let variant = match (field.is_array_kind(), field.required) {
+                (true, _) => "Array",
+                (false, true) => "Bytes",
+                (false, false) => "OptionalBytes",
+            };
+            syn::parse_str(variant).unwrap()
+        })
+        .collect();
+
+    let type_impl: TokenStream = parse_quote! {
+        impl #kind_ty {
+            /// Returns a copy of the value of the field with the provided name.
+            pub fn get_field(&self, name: &str) -> Option<FieldValue> {
+                match name {
+                    #(#field_names => Some(FieldValue::#field_variants(self.#field_idents.clone())),)*
+                    _ => None,
+                }
+            }
+
+            /// Sets the field with the provided name, if the value has the shape of the field.
+            pub fn set_field(&mut self, name: &str, value: FieldValue) -> Result<(), FieldError> {
+                match (name, value) {
+                    #((#field_names, FieldValue::#field_variants(value)) => self.#field_idents = value,)*
+                    #((#field_names, _) => return Err(FieldError::WrongShape { field: #field_names }),)*
+                    (name, _) => return Err(FieldError::UnknownField(name.to_owned())),
+                }
+                Ok(())
+            }
+        }
+    };
+    write!(writer, "{}", type_impl).unwrap();
+}
+
 fn write_entity_kind<W: Write>(writer: &mut W, kind_names: Vec<String>, kind_ids: Vec<u64>) {
     let variants = kind_names_types(&kind_names);
     // EntityKind
     {
         let type_impl: TokenStream = parse_quote! {
-            #[derive(Debug, Clone, PartialEq, strum_macros::EnumVariantNames)]
+            #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, strum_macros::EnumVariantNames)]
             pub enum EntityKind {
                 #(#variants),
                 *
@@ -445,6 +735,85 @@ fn write_entity_kind<W: Write>(writer: &mut W, kind_names: Vec<String>, kind_ids
     }
 }
 
+fn write_kind_schemas<W: Write>(writer: &mut W, kinds: &[Kind]) {
+    let kind_schemas: Vec<TokenStream> = kinds
+        .iter()
+        .map(|raw_kind| {
+            let data_field_names: Vec<String> = get_data_fields(&raw_kind.name, &raw_kind.fields)
+                .into_iter()
+                .map(|n| n.name)
+                .collect();
+            let field_schemas: Vec<TokenStream> = raw_kind
+                .fields
+                .iter()
+                .map(|field| {
+                    let name = &field.name;
+                    let kind = &field.kind;
+                    let required = field.required;
+                    let array = field.is_array_kind();
+                    let data = data_field_names.contains(&field.name);
+                    let min_cardinality: TokenStream = match field.minCardinality {
+                        Some(min) => parse_quote!(Some(#min)),
+                        None => parse_quote!(None),
+                    };
+                    parse_quote! {
+                        FieldSchema {
+                            name: #name,
+                            kind: #kind,
+                            required: #required,
+                            array: #array,
+                            data: #data,
+                            min_cardinality: #min_cardinality,
+                        }
+                    }
+                })
+                .collect();
+
+            let name = &raw_kind.name;
+            let expression_kind: TokenStream = match raw_kind.expressionKind {
+                Some(ref expression_kind) => parse_quote!(Some(#expression_kind)),
+                None => parse_quote!(None),
+            };
+            let kind_id = raw_kind.kindId;
+            let cid_prefix = raw_kind.cidPrefix;
+            parse_quote! {
+                KindSchema {
+                    name: #name,
+                    fields: &[#(#field_schemas),*],
+                    expression_kind: #expression_kind,
+                    kind_id: #kind_id,
+                    cid_prefix: #cid_prefix,
+                }
+            }
+        })
+        .collect();
+
+    let kind_count = kinds.len();
+    let schemas_static: TokenStream = parse_quote! {
+        static KIND_SCHEMAS: [KindSchema; #kind_count] = [#(#kind_schemas),*];
+    };
+    write!(writer, "{}", schemas_static).unwrap();
+
+    let kind_names: Vec<String> = kinds.iter().map(|n| n.name.to_owned()).collect();
+    let variants = kind_names_types(&kind_names);
+    let kind_indices: Vec<usize> = (0..kinds.len()).collect();
+    let type_impl: TokenStream = parse_quote! {
+        impl EntityKind {
+            /// Schema of all entity kinds, ordered by kind id.
+            pub fn schemas() -> &'static [KindSchema] {
+                &KIND_SCHEMAS
+            }
+
+            pub fn schema(&self) -> &'static KindSchema {
+                match self {
+                    #(EntityKind::#variants => &KIND_SCHEMAS[#kind_indices]),*
+                }
+            }
+        }
+    };
+    write!(writer, "{}", type_impl).unwrap();
+}
+
 fn write_entity<W: Write>(writer: &mut W, kind_names: Vec<String>) {
     let variants = kind_names_types(&kind_names);
 
@@ -453,6 +822,7 @@ fn write_entity<W: Write>(writer: &mut W, kind_names: Vec<String>) {
         let type_impl: TokenStream = parse_quote! {
             #[derive(Debug, Clone, PartialEq, Delegate)]
             #[delegate(Canonicalize)]
+            #[delegate(Validate)]
             #[cfg_attr(feature = "std", delegate(ToCid))]
             pub enum Entity {
                 #(#variants(#variants)),
@@ -559,6 +929,52 @@ fn write_entity<W: Write>(writer: &mut W, kind_names: Vec<String>) {
                         *
                     }
                 }
+
+                /// Names of the fields of the entity kind that hold CIDs, in the order of
+                /// `iter_cid_fields`.
+                pub fn cid_field_names(&self) -> &'static [&'static str] {
+                    match &self {
+                        #(Entity::#variants(_) => #variants::cid_field_names()),
+                        *
+                    }
+                }
+
+                /// Names of the fields of the entity kind that hold data.
+                pub fn data_field_names(&self) -> &'static [&'static str] {
+                    match &self {
+                        #(Entity::#variants(_) => #variants::data_field_names()),
+                        *
+                    }
+                }
+
+                /// Protobuf encoding of the canonicalized entity, as hashed for its CID.
+                #[cfg(feature = "std")]
+                pub fn canonical_encoding(&self) -> Vec<u8> {
+                    let mut canonical = self.clone();
+                    canonical.canonicalize();
+                    let mut encoded = Vec::new();
+                    match &canonical {
+                        #(Entity::#variants(ent) => ent.encode(&mut encoded).unwrap()),
+                        *
+                    }
+                    encoded
+                }
+
+                /// Returns a copy of the value of the field with the provided name.
+                pub fn get_field(&self, name: &str) -> Option<FieldValue> {
+                    match &self {
+                        #(Entity::#variants(ent) => ent.get_field(name)),
+                        *
+                    }
+                }
+
+                /// Sets the field with the provided name, if the value has the shape of the field.
+                pub fn set_field(&mut self, name: &str, value: FieldValue) -> Result<(), FieldError> {
+                    match self {
+                        #(Entity::#variants(ent) => ent.set_field(name, value)),
+                        *
+                    }
+                }
             }
         };
         write!(writer, "{}", type_impl).unwrap();
@@ -28,6 +28,9 @@ pub struct Field {
     pub kind: String,
     #[serde(default)]
     pub required: bool,
+    /// Minimum number of elements for array fields, as required by the grammar.
+    #[serde(default)]
+    pub minCardinality: Option<usize>,
 }
 
 impl Field {
@@ -12,6 +12,7 @@ extern crate syn;
 #[cfg(feature = "serde_json")]
 extern crate serde_json;
 
+mod builder;
 mod compact;
 mod core;
 mod entities;
@@ -41,6 +42,8 @@ pub fn build_files() {
     fmt_file("rlay.ontology.compact.rs");
     v0::build_file("src/intermediate.json", "rlay.ontology.v0.rs");
     fmt_file("rlay.ontology.v0.rs");
+    builder::build_file("src/intermediate.json", "rlay.ontology.builder.rs");
+    fmt_file("rlay.ontology.builder.rs");
 }
 
 fn fmt_file(path: &str) {
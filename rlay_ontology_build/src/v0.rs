@@ -26,6 +26,10 @@ pub fn build_file(src_path: &str, out_path: &str) {
 
 fn write_entity<W: Write>(writer: &mut W, kind_names: Vec<String>, kind_ids: Vec<u64>) {
     let variants = kind_names_types(&kind_names);
+    let compact_variants: Vec<syn::Type> = kind_names
+        .iter()
+        .map(|n| syn::parse_str(&format!("{}FormatCompact", n)).unwrap())
+        .collect();
 
     // Entity
     {
@@ -115,6 +119,24 @@ fn write_entity<W: Write>(writer: &mut W, kind_names: Vec<String>, kind_ids: Vec
                         _ => panic!("Unrecognized kind id.")
                     })
                 }
+
+                /// Deserializes the entity, only accepting the exact bytes that `serialize` would
+                /// produce.
+                #[cfg(feature = "std")]
+                pub fn deserialize_strict(bytes: &[u8]) -> Result<Self, canonical::CanonicalError> {
+                    let (version_number, version_len) = canonical::read_varint_strict(bytes)?;
+                    if version_number != 0 {
+                        return Err(canonical::CanonicalError::UnsupportedVersion(version_number));
+                    }
+
+                    let (kind_id, kind_id_len) = canonical::read_varint_strict(&bytes[version_len..])?;
+                    let payload = &bytes[(version_len + kind_id_len)..];
+                    Ok(match kind_id {
+                        #(#kind_ids => EntityV0::#variants(#compact_variants::from_slice_strict(payload)?.into())),
+                        *,
+                        _ => return Err(canonical::CanonicalError::UnknownKind(kind_id)),
+                    })
+                }
             }
         };
         write!(writer, "{}", trait_impl).unwrap();
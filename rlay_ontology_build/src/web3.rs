@@ -42,8 +42,16 @@ fn write_entity_impl_from_abiv2_response<W: Write>(writer: &mut W, raw_kind: &Ki
             let offset_ident = format_ident!("{}_offset", field.name.to_snake_case());
             let offset_start = i * 32;
             let offset_end = (i + 1) * 32;
-            let tokens: TokenStream = parse_quote! {
-                decode_offset!(bytes, #offset_ident, #offset_start, #offset_end);
+            let tokens: TokenStream = match i {
+                0 => parse_quote! {
+                    decode_offset!(bytes, #offset_ident, #offset_start, #offset_end);
+                },
+                _ => {
+                    let head_end = format_ident!("{}_offset", fields[0].name.to_snake_case());
+                    parse_quote! {
+                        decode_offset!(bytes, #offset_ident, #offset_start, #offset_end, #head_end);
+                    }
+                }
             };
             tokens
         })